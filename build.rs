@@ -0,0 +1,51 @@
+//! Generates the list of solutions that the `registry` binary runs in-process.
+//!
+//! Every `src/bin/DD.rs` and `src/bin/YYYY-DD.rs` is included as a module of the registry binary.
+//! The module exposes the `SOLUTION` constant that is emitted by the `solution!` macro.
+use std::{env, fs, path::Path};
+
+fn main() {
+    println!("cargo:rerun-if-changed=src/bin");
+
+    let bin_dir = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join("src/bin");
+
+    let mut days: Vec<(String, String)> = fs::read_dir(&bin_dir)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter_map(|path| {
+                    let stem = path.file_stem()?.to_str()?;
+                    let (year, day) = match stem.split_once('-') {
+                        Some((year, day)) => (Some(year), day),
+                        None => (None, stem),
+                    };
+                    let is_day = path.extension()? == "rs"
+                        && year.is_none_or(|y| y.len() == 4 && y.parse::<u16>().is_ok())
+                        && day.len() == 2
+                        && day.parse::<u8>().is_ok_and(|d| (1..=25).contains(&d));
+                    is_day.then(|| (stem.replace('-', "_"), path.display().to_string()))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    days.sort();
+
+    let mut out = String::new();
+
+    for (day, path) in &days {
+        out.push_str(&format!(
+            "#[path = {path:?}]\n#[allow(dead_code, unused, clippy::all)]\nmod day_{day};\n"
+        ));
+    }
+
+    out.push_str("\npub const SOLUTIONS: &[advent_of_code::template::registry::Solution] = &[\n");
+    for (day, _) in &days {
+        out.push_str(&format!("    day_{day}::SOLUTION,\n"));
+    }
+    out.push_str("];\n");
+
+    let out_path = Path::new(&env::var("OUT_DIR").unwrap()).join("solutions.rs");
+    fs::write(out_path, out).unwrap();
+}
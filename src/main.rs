@@ -1,78 +1,275 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::calendar::Calendar;
+use advent_of_code::template::commands::{
+    all, download, examples, read, report, scaffold, solve, stars, time, verify, watch,
+};
+use advent_of_code::template::readme_benchmarks::Metric;
+use advent_of_code::template::run_multi::RunOptions;
+use advent_of_code::template::Workspace;
 use args::{parse, AppArguments};
 
-#[cfg(feature = "today")]
-use advent_of_code::template::Day;
-#[cfg(feature = "today")]
 use std::process;
 
 mod args {
-    use advent_of_code::template::Day;
-    use std::process;
+    use advent_of_code::template::{
+        bench_config::BenchConfig, input::Source, report::Format, timeout::Timeouts, Day, Year,
+    };
+    use std::{path::PathBuf, process, time::Duration};
 
     pub enum AppArguments {
         Download {
             day: Day,
+            force: bool,
         },
         Read {
             day: Day,
+            part: Option<u8>,
+            plain: bool,
+        },
+        Examples {
+            day: Day,
+            yes: bool,
         },
         Scaffold {
             day: Day,
             download: bool,
             overwrite: bool,
+            force: bool,
+            template: Option<String>,
         },
         Solve {
             day: Day,
             release: bool,
             dhat: bool,
             submit: Option<u8>,
+            input: Source,
+            time: bool,
+            heap: bool,
+            bench: BenchConfig,
+            timeouts: Timeouts,
         },
         All {
             release: bool,
+            time: bool,
+            heap: bool,
+            bench: BenchConfig,
+            subprocess: bool,
+            jobs: usize,
+            timeouts: Timeouts,
         },
         Time {
             all: bool,
             day: Option<Day>,
             store: bool,
+            median: bool,
+            heap: bool,
+            bench: BenchConfig,
+            subprocess: bool,
+            compare_threshold: Option<f64>,
+            timeouts: Timeouts,
+        },
+        History {
+            day: Day,
+        },
+        Verify {
+            day: Option<Day>,
+            record: bool,
+            subprocess: bool,
+            timeouts: Timeouts,
+        },
+        Watch {
+            day: Day,
+            release: bool,
+            timeouts: Timeouts,
+        },
+        Stars,
+        Report {
+            format: Option<Format>,
+            output: Option<PathBuf>,
+            readme: bool,
+            median: bool,
         },
         #[cfg(feature = "today")]
         Today,
     }
 
-    pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
+    impl AppArguments {
+        /// The day selected by the command, if any.
+        pub fn day(&self) -> Option<Day> {
+            match self {
+                AppArguments::Download { day, .. }
+                | AppArguments::Read { day, .. }
+                | AppArguments::Examples { day, .. }
+                | AppArguments::Scaffold { day, .. }
+                | AppArguments::Solve { day, .. }
+                | AppArguments::History { day }
+                | AppArguments::Watch { day, .. } => Some(*day),
+                AppArguments::Time { day, .. } | AppArguments::Verify { day, .. } => *day,
+                _ => None,
+            }
+        }
+    }
+
+    /// Parse benchmark settings. Flags take precedence over `AOC_BENCH_*` environment variables.
+    fn parse_bench_config(
+        args: &mut pico_args::Arguments,
+    ) -> Result<BenchConfig, Box<dyn std::error::Error>> {
+        let mut config = BenchConfig::from_env()?;
+
+        if let Some(budget) = args.opt_value_from_str("--budget")? {
+            config.budget = Duration::from_millis(budget);
+        }
+        if let Some(min_samples) = args.opt_value_from_str("--min-samples")? {
+            config.min_samples = min_samples;
+        }
+        if let Some(max_samples) = args.opt_value_from_str("--max-samples")? {
+            config.max_samples = max_samples;
+        }
+        if let Some(warmup) = args.opt_value_from_str("--warmup")? {
+            config.warmup = warmup;
+        }
+
+        Ok(config.validate()?)
+    }
+
+    /// Parse `--timeout <ms>` and `--timeout <day>=<ms>` flags.
+    /// Flags take precedence over `AOC_TIMEOUT_MS*` environment variables.
+    fn parse_timeouts(
+        args: &mut pico_args::Arguments,
+    ) -> Result<Timeouts, Box<dyn std::error::Error>> {
+        let mut timeouts = Timeouts::from_env();
+        let flags = Timeouts::parse(&args.values_from_str::<_, String>("--timeout")?)?;
+
+        timeouts.default = flags.default.or(timeouts.default);
+        timeouts.days.extend(flags.days);
+
+        Ok(timeouts)
+    }
+
+    /// Parse `--input <path>` (`-` for stdin) and `--example [<n>]`.
+    /// Must be called last, as the number of the example is an optional free argument.
+    fn parse_input(args: &mut pico_args::Arguments) -> Result<Source, Box<dyn std::error::Error>> {
+        let input: Option<String> = args.opt_value_from_str("--input")?;
+        let example = args.contains("--example");
+
+        match (input, example) {
+            (Some(_), true) => Err("`--input` and `--example` cannot be combined.".into()),
+            (Some(path), false) if path == "-" => Ok(Source::Stdin),
+            (Some(path), false) => Ok(Source::File(path.into())),
+            (None, true) => Ok(Source::Example(args.opt_free_from_str()?)),
+            (None, false) => Ok(Source::Puzzle),
+        }
+    }
+
+    /// Parse the command and the year it applies to.
+    /// The year is set with `--year` on any command and defaults to `AOC_YEAR`.
+    pub fn parse() -> Result<(AppArguments, Option<Year>), Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
 
-        let app_args = match args.subcommand()?.as_deref() {
+        let subcommand = args.subcommand()?;
+        let year = args.opt_value_from_str("--year")?.or_else(Year::from_env);
+
+        let app_args = match subcommand.as_deref() {
             Some("all") => AppArguments::All {
                 release: args.contains("--release"),
+                time: args.contains("--time"),
+                heap: args.contains("--heap"),
+                bench: parse_bench_config(&mut args)?,
+                subprocess: args.contains("--subprocess"),
+                jobs: args.opt_value_from_str("--jobs")?.unwrap_or(1),
+                timeouts: parse_timeouts(&mut args)?,
+            },
+            Some("time") if args.contains("--history") => AppArguments::History {
+                day: args.free_from_str()?,
             },
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let median = args.contains("--median");
+                let heap = args.contains("--heap");
+                let subprocess = args.contains("--subprocess");
+                let bench = parse_bench_config(&mut args)?;
+                let compare = args.contains("--compare");
+                let threshold = args.opt_value_from_str("--threshold")?.unwrap_or(10.0);
+                let timeouts = parse_timeouts(&mut args)?;
 
                 AppArguments::Time {
                     all,
                     day: args.opt_free_from_str()?,
                     store,
+                    median,
+                    heap,
+                    bench,
+                    subprocess,
+                    compare_threshold: compare.then_some(threshold),
+                    timeouts,
                 }
             }
             Some("download") => AppArguments::Download {
                 day: args.free_from_str()?,
+                force: args.contains("--force"),
             },
             Some("read") => AppArguments::Read {
                 day: args.free_from_str()?,
+                part: args.opt_value_from_str("--part")?,
+                plain: args.contains("--plain"),
+            },
+            Some("examples") => AppArguments::Examples {
+                day: args.free_from_str()?,
+                yes: args.contains("--yes"),
             },
             Some("scaffold") => AppArguments::Scaffold {
                 day: args.free_from_str()?,
                 download: args.contains("--download"),
                 overwrite: args.contains("--overwrite"),
+                force: args.contains("--force"),
+                template: args.opt_value_from_str("--template")?,
             },
-            Some("solve") => AppArguments::Solve {
+            Some("solve") => {
+                let day = args.free_from_str()?;
+                let release = args.contains("--release");
+                let submit = args.opt_value_from_str("--submit")?;
+                let dhat = args.contains("--dhat");
+                let time = args.contains("--time");
+                let heap = args.contains("--heap");
+                let bench = parse_bench_config(&mut args)?;
+                let timeouts = parse_timeouts(&mut args)?;
+                let input = parse_input(&mut args)?;
+
+                AppArguments::Solve {
+                    day,
+                    release,
+                    submit,
+                    input,
+                    dhat,
+                    time,
+                    heap,
+                    bench,
+                    timeouts,
+                }
+            }
+            Some("verify") => {
+                let record = args.contains("--record");
+                let subprocess = args.contains("--subprocess");
+                let timeouts = parse_timeouts(&mut args)?;
+
+                AppArguments::Verify {
+                    day: args.opt_free_from_str()?,
+                    record,
+                    subprocess,
+                    timeouts,
+                }
+            }
+            Some("watch") => AppArguments::Watch {
                 day: args.free_from_str()?,
                 release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                dhat: args.contains("--dhat"),
+                timeouts: parse_timeouts(&mut args)?,
+            },
+            Some("stars") => AppArguments::Stars,
+            Some("report") => AppArguments::Report {
+                format: args.opt_value_from_str("--format")?,
+                output: args.opt_value_from_str("--output")?,
+                readme: args.contains("--readme"),
+                median: args.contains("--median"),
             },
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
@@ -91,54 +288,175 @@ mod args {
             eprintln!("Warning: unknown argument(s): {remaining:?}.");
         }
 
-        Ok(app_args)
+        Ok((app_args, year))
     }
 }
 
 fn main() {
-    match parse() {
+    let (args, year) = match parse() {
         Err(err) => {
             eprintln!("Error: {err}");
             std::process::exit(1);
         }
-        Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold {
-                day,
-                download,
-                overwrite,
-            } => {
-                scaffold::handle(day, overwrite);
-                if download {
-                    download::handle(day);
-                }
-            }
-            AppArguments::Solve {
+        Ok(args) => args,
+    };
+
+    let workspace = Workspace::locate(year);
+
+    let calendar = match Calendar::from_env(workspace.year) {
+        Ok(calendar) => calendar,
+        Err(err) => {
+            eprintln!("Error: {err}");
+            process::exit(1);
+        }
+    };
+
+    if let Some(day) = args.day().filter(|day| !calendar.contains(*day)) {
+        eprintln!(
+            "Error: day {day} is not part of the event, which ends on day {}.",
+            calendar.last_day()
+        );
+        process::exit(1);
+    }
+
+    match args {
+        AppArguments::All {
+            release,
+            time,
+            heap,
+            bench,
+            subprocess,
+            jobs,
+            timeouts,
+        } => all::handle(&RunOptions {
+            is_release: release,
+            is_timed: time,
+            bench,
+            subprocess,
+            jobs,
+            timeouts,
+            heap,
+            workspace,
+            calendar,
+        }),
+        AppArguments::Time {
+            day,
+            all,
+            store,
+            median,
+            heap,
+            bench,
+            subprocess,
+            compare_threshold,
+            timeouts,
+        } => {
+            let metric = if median { Metric::Median } else { Metric::Mean };
+            time::handle(
                 day,
-                release,
-                dhat,
-                submit,
-            } => solve::handle(day, release, dhat, submit),
-            #[cfg(feature = "today")]
-            AppArguments::Today => {
-                match Day::today() {
-                    Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
-                    }
-                    None => {
-                        eprintln!(
-                            "`today` command can only be run between the 1st and \
-                            the 25th of december. Please use `scaffold` with a specific day."
-                        );
-                        process::exit(1)
-                    }
-                };
+                all,
+                store,
+                metric,
+                compare_threshold,
+                RunOptions {
+                    bench,
+                    subprocess,
+                    timeouts,
+                    heap,
+                    workspace,
+                    calendar,
+                    ..Default::default()
+                },
+            );
+        }
+        AppArguments::Download { day, force } => download::handle(workspace, day, force),
+        AppArguments::Read { day, part, plain } => read::handle(workspace, day, part, plain),
+        AppArguments::Examples { day, yes } => examples::handle(workspace, day, yes),
+        AppArguments::Scaffold {
+            day,
+            download,
+            overwrite,
+            force,
+            template,
+        } => {
+            // download first, so that the template can use the title of the puzzle.
+            if download {
+                download::handle(workspace, day, force);
             }
-        },
+            scaffold::handle(workspace, day, overwrite, template.as_deref());
+        }
+        AppArguments::Solve {
+            day,
+            release,
+            dhat,
+            submit,
+            input,
+            time,
+            heap,
+            bench,
+            timeouts,
+        } => solve::handle(
+            day,
+            dhat,
+            submit,
+            &input,
+            &RunOptions {
+                is_release: release,
+                is_timed: time,
+                bench,
+                timeouts,
+                heap,
+                workspace,
+                ..Default::default()
+            },
+        ),
+        AppArguments::History { day } => time::history(workspace.year, day),
+        AppArguments::Verify {
+            day,
+            record,
+            subprocess,
+            timeouts,
+        } => verify::handle(workspace, calendar, day, record, subprocess, timeouts),
+        AppArguments::Watch {
+            day,
+            release,
+            timeouts,
+        } => watch::handle(
+            day,
+            &RunOptions {
+                is_release: release,
+                timeouts,
+                workspace,
+                ..Default::default()
+            },
+        ),
+        AppArguments::Stars => stars::handle(workspace, calendar),
+        AppArguments::Report {
+            format,
+            output,
+            readme,
+            median,
+        } => {
+            let metric = if median { Metric::Median } else { Metric::Mean };
+            report::handle(format, output, readme, metric);
+        }
+        #[cfg(feature = "today")]
+        AppArguments::Today => {
+            match calendar.today() {
+                Some(day) => {
+                    download::handle(workspace, day, false);
+                    scaffold::handle(workspace, day, false, None);
+                    read::handle(workspace, day, None, false)
+                }
+                None => {
+                    let (month, day) = calendar.start;
+                    eprintln!(
+                        "`today` command can only be run while the event is running, \
+                        {} days starting on {month:02}-{day:02}. Please use `scaffold` with a specific day.",
+                        calendar.days
+                    );
+                    process::exit(1)
+                }
+            };
+        }
     };
 }
@@ -0,0 +1,7 @@
+//! Runs every scaffolded solution in a single process.
+//! The list of solutions is generated by `build.rs` from the contents of `src/bin`.
+include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+
+fn main() {
+    advent_of_code::template::registry::run(SOLUTIONS);
+}
@@ -0,0 +1,241 @@
+/// Known-good answers, used to detect when a change alters the result of a solved day.
+use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::{Day, Workspace};
+
+/// Stored in the data folder of each workspace, e.g. `data/2024/answers.json`.
+static ANSWERS_FILE_NAME: &str = "answers.json";
+
+/// Represents the recorded answers for a single day.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Answer {
+    pub day: Day,
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+/// Represents the recorded answers for a set of days.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Answers {
+    pub data: Vec<Answer>,
+}
+
+/// The outcome of comparing a result with the recorded answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verification {
+    /// No answer has been recorded for this part yet.
+    Unknown,
+    Correct,
+    Incorrect {
+        expected: String,
+    },
+}
+
+impl Answers {
+    /// Dehydrate answers to the JSON file of a workspace.
+    pub fn store_file(&self, workspace: &Workspace) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        // the data folder of a year namespaced by year may not exist yet.
+        fs::create_dir_all(workspace.data_dir())?;
+        let mut file = fs::File::create(workspace.data_dir().join(ANSWERS_FILE_NAME))?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate answers from the JSON file of a workspace. If not present, returns empty answers.
+    pub fn read_from_file(workspace: &Workspace) -> Self {
+        let path = workspace.data_dir().join(ANSWERS_FILE_NAME);
+        let Ok(s) = fs::read_to_string(&path) else {
+            return Answers::default();
+        };
+
+        match Answers::try_from(s) {
+            Ok(answers) => answers,
+            Err(e) => {
+                eprintln!("{}: {e}", path.display());
+                Answers::default()
+            }
+        }
+    }
+
+    /// Get the recorded answer for a part, if any.
+    pub fn get(&self, day: Day, part: u8) -> Option<&str> {
+        let answer = self.data.iter().find(|a| a.day == day)?;
+        match part {
+            1 => answer.part_1.as_deref(),
+            2 => answer.part_2.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Record the answer for a part, replacing a previously recorded answer.
+    pub fn record(&mut self, day: Day, part: u8, value: &str) {
+        let index = match self.data.iter().position(|a| a.day == day) {
+            Some(index) => index,
+            None => {
+                self.data.push(Answer {
+                    day,
+                    part_1: None,
+                    part_2: None,
+                });
+                self.data.sort_unstable_by_key(|a| a.day);
+                self.data.iter().position(|a| a.day == day).unwrap()
+            }
+        };
+
+        let answer = &mut self.data[index];
+        match part {
+            1 => answer.part_1 = Some(value.into()),
+            2 => answer.part_2 = Some(value.into()),
+            _ => {}
+        }
+    }
+
+    /// Compare a result with the recorded answer for a part.
+    pub fn verify(&self, day: Day, part: u8, result: &str) -> Verification {
+        match self.get(day, part) {
+            None => Verification::Unknown,
+            Some(expected) if expected == result => Verification::Correct,
+            Some(expected) => Verification::Incorrect {
+                expected: expected.into(),
+            },
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Answers> for JsonValue {
+    fn from(value: Answers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Answers {
+            data: json_data
+                .iter()
+                .map(Answer::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Answer> for JsonValue {
+    fn from(value: &Answer) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+
+        let to_json = |part: &Option<String>| match part {
+            Some(x) => JsonValue::String(x.clone()),
+            None => JsonValue::Null,
+        };
+
+        map.insert("part_1".into(), to_json(&value.part_1));
+        map.insert("part_2".into(), to_json(&value.part_2));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Answer {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected answer to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected answer.day to be a Day struct.")?;
+
+        let part_1 = json
+            .get("part_1")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected answer.part_1 to be null or string.")?;
+
+        let part_2 = json
+            .get("part_2")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected answer.part_2 to be null or string.")?;
+
+        Ok(Answer {
+            day,
+            part_1: part_1.cloned(),
+            part_2: part_2.cloned(),
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Answers, Verification};
+    use crate::day;
+
+    #[test]
+    fn records_and_verifies_answers() {
+        let mut answers = Answers::default();
+        answers.record(day!(9), 2, "6321896265143");
+        answers.record(day!(1), 1, "11");
+
+        assert_eq!(answers.data[0].day, day!(1));
+        assert_eq!(answers.verify(day!(1), 1, "11"), Verification::Correct);
+        assert_eq!(
+            answers.verify(day!(9), 2, "42"),
+            Verification::Incorrect {
+                expected: "6321896265143".into()
+            }
+        );
+        assert_eq!(answers.verify(day!(9), 1, "42"), Verification::Unknown);
+    }
+
+    #[test]
+    fn overwrites_recorded_answers() {
+        let mut answers = Answers::default();
+        answers.record(day!(1), 1, "11");
+        answers.record(day!(1), 1, "12");
+        assert_eq!(answers.data.len(), 1);
+        assert_eq!(answers.get(day!(1), 1), Some("12"));
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let json = r#"{ "data": [{ "day": "01", "part_1": "11", "part_2": null }] }"#.to_string();
+        let answers = Answers::try_from(json).unwrap();
+        assert_eq!(answers.get(day!(1), 1), Some("11"));
+        assert_eq!(answers.get(day!(1), 2), None);
+
+        let json = tinyjson::JsonValue::from(answers).stringify().unwrap();
+        let answers = Answers::try_from(json).unwrap();
+        assert_eq!(answers.get(day!(1), 1), Some("11"));
+    }
+}
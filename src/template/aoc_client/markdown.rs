@@ -0,0 +1,214 @@
+/// Minimal conversion of puzzle pages to markdown, handling only the elements that puzzle descriptions and submission responses use.
+/// Converts the `<article>` elements of a page, or the whole page if it has none.
+pub fn to_markdown(html: &str) -> String {
+    let articles = extract_articles(html);
+
+    let markdown = if articles.is_empty() {
+        convert(html)
+    } else {
+        articles
+            .iter()
+            .map(|a| convert(a))
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    };
+
+    let mut result = String::new();
+    let mut blank_lines = 0;
+
+    // collapse runs of blank lines, trimming trailing whitespace from each line.
+    for line in markdown.trim().lines() {
+        let line = line.trim_end();
+        if line.is_empty() {
+            blank_lines += 1;
+            if blank_lines > 1 {
+                continue;
+            }
+        } else {
+            blank_lines = 0;
+        }
+        result.push_str(line);
+        result.push('\n');
+    }
+
+    result
+}
+
+/// The title of a puzzle description, e.g. `Day 1: Historian Hysteria`.
+pub fn puzzle_title(markdown: &str) -> Option<&str> {
+    markdown.lines().find_map(|line| {
+        line.strip_prefix("## --- ")
+            .and_then(|title| title.strip_suffix(" ---"))
+    })
+}
+
+fn extract_articles(html: &str) -> Vec<&str> {
+    let mut articles = vec![];
+    let mut rest = html;
+
+    while let Some(start) = rest.find("<article") {
+        let Some(open_end) = rest[start..].find('>') else {
+            break;
+        };
+        let content_start = start + open_end + 1;
+        let Some(len) = rest[content_start..].find("</article>") else {
+            break;
+        };
+        articles.push(&rest[content_start..content_start + len]);
+        rest = &rest[content_start + len..];
+    }
+
+    articles
+}
+
+fn convert(html: &str) -> String {
+    let mut out = String::new();
+    let mut links: Vec<String> = vec![];
+    let mut in_pre = false;
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        let Some(tag_start) = rest.find('<') else {
+            push_text(&mut out, rest, in_pre);
+            break;
+        };
+
+        push_text(&mut out, &rest[..tag_start], in_pre);
+
+        let Some(tag_len) = rest[tag_start..].find('>') else {
+            break;
+        };
+        let tag = &rest[tag_start + 1..tag_start + tag_len];
+        rest = &rest[tag_start + tag_len + 1..];
+
+        let is_closing = tag.starts_with('/');
+        let name = tag
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default()
+            .to_lowercase();
+
+        match (name.as_str(), is_closing) {
+            ("h2", false) => out.push_str("\n## "),
+            ("h2" | "p" | "ul", true) => out.push_str("\n\n"),
+            ("pre", false) => {
+                in_pre = true;
+                out.push_str("\n```\n");
+            }
+            ("pre", true) => {
+                in_pre = false;
+                if !out.ends_with('\n') {
+                    out.push('\n');
+                }
+                out.push_str("```\n\n");
+            }
+            ("code", _) if !in_pre => out.push('`'),
+            // emphasis is not rendered in code blocks and would change examples.
+            ("em", _) if !in_pre => out.push('*'),
+            ("li", false) => out.push_str("- "),
+            ("li", true) => out.push('\n'),
+            ("a", false) => {
+                links.push(attribute(tag, "href").unwrap_or_default());
+                out.push('[');
+            }
+            ("a", true) => {
+                let href = links.pop().unwrap_or_default();
+                out.push_str(&format!("]({href})"));
+            }
+            ("br", _) => out.push('\n'),
+            _ => {}
+        }
+    }
+
+    out
+}
+
+fn push_text(out: &mut String, text: &str, in_pre: bool) {
+    let text = decode_entities(text);
+
+    if in_pre {
+        out.push_str(&text);
+        return;
+    }
+
+    // whitespace between block elements carries no meaning.
+    if text.trim().is_empty() {
+        if !text.is_empty() && !out.ends_with(char::is_whitespace) && !out.is_empty() {
+            out.push(' ');
+        }
+        return;
+    }
+
+    out.push_str(&text.replace('\n', " "));
+}
+
+fn attribute(tag: &str, name: &str) -> Option<String> {
+    let start = tag.find(&format!("{name}=\""))? + name.len() + 2;
+    let len = tag[start..].find('"')?;
+    Some(decode_entities(&tag[start..start + len]))
+}
+
+fn decode_entities(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{puzzle_title, to_markdown};
+
+    #[test]
+    fn converts_puzzle_descriptions() {
+        let html = r#"<html><body><main>
+<article class="day-desc"><h2>--- Day 1: Historian Hysteria ---</h2>
+<p>The <em>Chief Historian</em> is always present.</p>
+<p>For example:</p>
+<pre><code>3   4
+4   3
+</code></pre>
+<ul><li>The <code>1</code> in the left list &amp; <a href="/2024/about">more</a>.</li></ul>
+</article>
+<p>Answer: <input type="text" name="answer"/></p>
+</main></body></html>"#;
+
+        let expected = [
+            "## --- Day 1: Historian Hysteria ---",
+            "",
+            "The *Chief Historian* is always present.",
+            "",
+            "For example:",
+            "",
+            "```",
+            "3   4",
+            "4   3",
+            "```",
+            "",
+            "- The `1` in the left list & [more](/2024/about).",
+            "",
+        ]
+        .join("\n");
+
+        assert_eq!(to_markdown(html), expected);
+    }
+
+    #[test]
+    fn joins_multiple_articles() {
+        let html = "<article><p>Part one.</p></article><p>Your answer was...</p><article><p>Part two.</p></article>";
+        assert_eq!(to_markdown(html), "Part one.\n\nPart two.\n");
+    }
+
+    #[test]
+    fn finds_puzzle_title() {
+        let markdown = "## --- Day 1: Historian Hysteria ---\n\nThe *Chief Historian*...\n";
+        assert_eq!(puzzle_title(markdown), Some("Day 1: Historian Hysteria"));
+        assert_eq!(puzzle_title("Part one."), None);
+    }
+}
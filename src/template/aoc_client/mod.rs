@@ -0,0 +1,345 @@
+/// Built-in client for the Advent of Code website.
+///
+/// Authenticates with the session cookie from `AOC_SESSION` or from a `.adventofcode.session` file,
+/// the same file that `aoc-cli` reads.
+use std::{env, fmt::Display, fs, io, path::PathBuf, time::Duration};
+
+use crate::template::{Day, Year};
+
+mod markdown;
+mod throttle;
+
+pub use markdown::{puzzle_title, to_markdown};
+pub use throttle::Throttle;
+
+pub const SESSION_ENV: &str = "AOC_SESSION";
+/// Overrides the website, e.g. to point the client at a local server.
+pub const BASE_URL_ENV: &str = "AOC_BASE_URL";
+/// Minimum interval between two requests, in milliseconds.
+pub const THROTTLE_ENV: &str = "AOC_THROTTLE_MS";
+
+static DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+static SESSION_FILE_NAME: &str = ".adventofcode.session";
+static USER_AGENT: &str = "github.com/fspoettel/advent-of-code-rust";
+static LAST_REQUEST_FILE_PATH: &str = "./data/.aoc_last_request";
+static DEFAULT_THROTTLE: Duration = Duration::from_secs(3);
+
+#[derive(Debug)]
+pub enum AocError {
+    /// No session cookie is configured, or the website did not accept it.
+    Unauthenticated,
+    /// The website asked to wait before trying again.
+    RateLimited {
+        message: String,
+    },
+    /// The puzzle of the requested day has not been released yet.
+    NotUnlocked,
+    /// Any other unexpected response.
+    Http {
+        status: u16,
+        message: String,
+    },
+    /// The website could not be reached.
+    Transport(String),
+    IO(io::Error),
+}
+
+impl Display for AocError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocError::Unauthenticated => write!(
+                f,
+                "not logged in. Set `{SESSION_ENV}` or store your session cookie in `~/{SESSION_FILE_NAME}`."
+            ),
+            AocError::RateLimited { message } => write!(f, "rate limited: {message}"),
+            AocError::NotUnlocked => write!(f, "this puzzle has not been unlocked yet."),
+            AocError::Http { status, message } => {
+                write!(f, "unexpected response ({status}): {message}")
+            }
+            AocError::Transport(e) => write!(f, "could not reach the website: {e}"),
+            AocError::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<io::Error> for AocError {
+    fn from(e: io::Error) -> Self {
+        AocError::IO(e)
+    }
+}
+
+pub struct Client {
+    base_url: String,
+    session: String,
+    year: Year,
+    agent: ureq::Agent,
+    throttle: Option<Throttle>,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: &str, year: Year) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').into(),
+            session: session.trim().into(),
+            year,
+            agent: ureq::AgentBuilder::new()
+                .user_agent(USER_AGENT)
+                .timeout(Duration::from_secs(30))
+                .build(),
+            throttle: None,
+        }
+    }
+
+    /// Wait for `throttle` before each request.
+    #[must_use]
+    pub fn with_throttle(mut self, throttle: Throttle) -> Self {
+        self.throttle = Some(throttle);
+        self
+    }
+
+    /// Create a client from the environment for the puzzles of `year`.
+    /// The year defaults to `AOC_YEAR`, then to the most recent event.
+    /// Requests are throttled to one every `AOC_THROTTLE_MS`, three seconds by default.
+    pub fn from_env(year: Option<Year>) -> Result<Self, AocError> {
+        let session = read_session().ok_or(AocError::Unauthenticated)?;
+        let base_url = env::var(BASE_URL_ENV).unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+        let year = year.or_else(Year::from_env).unwrap_or_else(Year::latest);
+
+        let interval = env::var(THROTTLE_ENV)
+            .ok()
+            .and_then(|x| x.parse().ok())
+            .map_or(DEFAULT_THROTTLE, Duration::from_millis);
+
+        Ok(Self::new(&base_url, &session, year)
+            .with_throttle(Throttle::new(LAST_REQUEST_FILE_PATH, interval)))
+    }
+
+    pub fn year(&self) -> Year {
+        self.year
+    }
+
+    /// Download the puzzle input of a day.
+    pub fn input(&self, day: Day) -> Result<String, AocError> {
+        self.get(&format!("{}/input", self.day_url(day)))
+    }
+
+    /// Download the puzzle description of a day as HTML.
+    pub fn puzzle_html(&self, day: Day) -> Result<String, AocError> {
+        self.get(&self.day_url(day))
+    }
+
+    /// Download the puzzle description of a day, converted to markdown.
+    pub fn puzzle(&self, day: Day) -> Result<String, AocError> {
+        self.puzzle_html(day).map(|html| to_markdown(&html))
+    }
+
+    /// Submit an answer and return the website's response, converted to markdown.
+    pub fn submit(&self, day: Day, part: u8, answer: &str) -> Result<String, AocError> {
+        self.throttle();
+
+        let response = self
+            .agent
+            .post(&format!("{}/answer", self.day_url(day)))
+            .set("Cookie", &self.cookie())
+            .send_form(&[("level", &part.to_string()), ("answer", answer)]);
+
+        let message = to_markdown(&read_response(response)?);
+
+        if message.contains("You gave an answer too recently") {
+            return Err(AocError::RateLimited { message });
+        }
+
+        Ok(message)
+    }
+
+    fn day_url(&self, day: Day) -> String {
+        format!("{}/{}/day/{}", self.base_url, self.year, day.into_inner())
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+
+    fn throttle(&self) {
+        if let Some(throttle) = &self.throttle {
+            throttle.wait();
+        }
+    }
+
+    fn get(&self, url: &str) -> Result<String, AocError> {
+        self.throttle();
+
+        let response = self.agent.get(url).set("Cookie", &self.cookie()).call();
+        read_response(response)
+    }
+}
+
+/// Map a response to its body, or to the error that matches its status.
+fn read_response(response: Result<ureq::Response, ureq::Error>) -> Result<String, AocError> {
+    match response {
+        Ok(response) => Ok(response.into_string()?),
+        Err(ureq::Error::Status(status, response)) => {
+            let message = response
+                .into_string()
+                .unwrap_or_default()
+                .trim()
+                .to_string();
+            Err(match status {
+                // the website answers requests with a missing or expired session with a 400.
+                400 | 401 | 403 => AocError::Unauthenticated,
+                404 => AocError::NotUnlocked,
+                429 => AocError::RateLimited { message },
+                status => AocError::Http { status, message },
+            })
+        }
+        Err(ureq::Error::Transport(e)) => Err(AocError::Transport(e.to_string())),
+    }
+}
+
+/// Read the session cookie from `AOC_SESSION`, `~/.adventofcode.session` or `~/.config/adventofcode.session`.
+fn read_session() -> Option<String> {
+    if let Ok(session) = env::var(SESSION_ENV) {
+        return Some(session).filter(|s| !s.trim().is_empty());
+    }
+
+    let home = PathBuf::from(env::var("HOME").or_else(|_| env::var("USERPROFILE")).ok()?);
+
+    [
+        home.join(SESSION_FILE_NAME),
+        home.join(".config").join(&SESSION_FILE_NAME[1..]),
+    ]
+    .iter()
+    .find_map(|path| fs::read_to_string(path).ok())
+    .map(|s| s.trim().to_string())
+    .filter(|s| !s.is_empty())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{AocError, Client};
+    use crate::{day, year};
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        sync::mpsc,
+        thread,
+    };
+
+    /// Serve a single canned response on a local port.
+    /// Returns the base url and a receiver for the raw request.
+    fn stub_server(status: &'static str, body: &'static str) -> (String, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let (tx, rx) = mpsc::channel();
+
+        thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut request = String::new();
+            let mut content_length = 0;
+
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(len) = line.to_lowercase().strip_prefix("content-length:") {
+                    content_length = len.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+
+            let mut body_bytes = vec![0; content_length];
+            reader.read_exact(&mut body_bytes).unwrap();
+            request.push_str(&String::from_utf8_lossy(&body_bytes));
+
+            let response = format!(
+                "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            reader.get_mut().write_all(response.as_bytes()).unwrap();
+            tx.send(request).unwrap();
+        });
+
+        (base_url, rx)
+    }
+
+    #[test]
+    fn downloads_input() {
+        let (base_url, rx) = stub_server("200 OK", "1\n2\n3\n");
+        let client = Client::new(&base_url, "abc", year!(2024));
+
+        assert_eq!(client.input(day!(7)).unwrap(), "1\n2\n3\n");
+
+        let request = rx.recv().unwrap();
+        assert!(request.starts_with("GET /2024/day/7/input "));
+        assert!(request.contains("session=abc"));
+    }
+
+    #[test]
+    fn reports_locked_puzzles() {
+        let (base_url, _rx) = stub_server(
+            "404 Not Found",
+            "Please don't repeatedly request this endpoint before it unlocks!",
+        );
+        let client = Client::new(&base_url, "abc", year!(2024));
+        assert!(matches!(client.input(day!(25)), Err(AocError::NotUnlocked)));
+    }
+
+    #[test]
+    fn reports_missing_login() {
+        let (base_url, _rx) = stub_server(
+            "400 Bad Request",
+            "Puzzle inputs differ by user.  Please log in to get your puzzle input.",
+        );
+        let client = Client::new(&base_url, "expired", year!(2024));
+        assert!(matches!(
+            client.input(day!(1)),
+            Err(AocError::Unauthenticated)
+        ));
+    }
+
+    #[test]
+    fn reports_server_errors() {
+        let (base_url, _rx) = stub_server("500 Internal Server Error", "down for maintenance");
+        let client = Client::new(&base_url, "abc", year!(2024));
+        assert!(matches!(
+            client.input(day!(1)),
+            Err(AocError::Http { status: 500, .. })
+        ));
+    }
+
+    #[test]
+    fn submits_answers() {
+        let (base_url, rx) = stub_server(
+            "200 OK",
+            "<main><article><p>That's the right answer!</p></article></main>",
+        );
+        let client = Client::new(&base_url, "abc", year!(2024));
+
+        assert_eq!(
+            client.submit(day!(1), 2, "42").unwrap(),
+            "That's the right answer!\n"
+        );
+
+        let request = rx.recv().unwrap();
+        assert!(request.starts_with("POST /2024/day/1/answer "));
+        assert!(request.ends_with("level=2&answer=42"));
+    }
+
+    #[test]
+    fn reports_rate_limited_submissions() {
+        let (base_url, _rx) = stub_server(
+            "200 OK",
+            "<article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 41s left to wait.</p></article>",
+        );
+        let client = Client::new(&base_url, "abc", year!(2024));
+        assert!(matches!(
+            client.submit(day!(1), 1, "42"),
+            Err(AocError::RateLimited { .. })
+        ));
+    }
+}
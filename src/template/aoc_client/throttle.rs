@@ -0,0 +1,85 @@
+/// Keeps a minimum interval between requests to the website, across invocations of the tool.
+use std::{
+    fs,
+    path::PathBuf,
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+pub struct Throttle {
+    /// File that holds the time of the last request, in milliseconds since the unix epoch.
+    path: PathBuf,
+    interval: Duration,
+}
+
+impl Throttle {
+    pub fn new(path: impl Into<PathBuf>, interval: Duration) -> Self {
+        Self {
+            path: path.into(),
+            interval,
+        }
+    }
+
+    /// Block until `interval` has passed since the last request, then persist the current time.
+    pub fn wait(&self) {
+        if let Some(last) = self.last_request() {
+            let elapsed = now().saturating_sub(last);
+
+            if let Some(remaining) = self.interval.checked_sub(elapsed) {
+                if !remaining.is_zero() {
+                    eprintln!("Waiting {remaining:.1?} before the next request...");
+                    thread::sleep(remaining);
+                }
+            }
+        }
+
+        if let Err(e) = fs::write(&self.path, now().as_millis().to_string()) {
+            eprintln!("Failed to persist the time of the last request: {e}");
+        }
+    }
+
+    fn last_request(&self) -> Option<Duration> {
+        fs::read_to_string(&self.path)
+            .ok()?
+            .trim()
+            .parse()
+            .ok()
+            .map(Duration::from_millis)
+    }
+}
+
+fn now() -> Duration {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Throttle;
+    use std::{
+        env, fs, process,
+        time::{Duration, Instant},
+    };
+
+    #[test]
+    fn waits_between_requests() {
+        let path = env::temp_dir().join(format!("aoc-throttle-{}", process::id()));
+        let _ = fs::remove_file(&path);
+
+        let throttle = Throttle::new(&path, Duration::from_millis(200));
+        let timer = Instant::now();
+
+        throttle.wait();
+        assert!(timer.elapsed() < Duration::from_millis(200));
+
+        // a new instance picks up the persisted timestamp.
+        Throttle::new(&path, Duration::from_millis(200)).wait();
+        // NOTE: timestamps are persisted with millisecond precision.
+        assert!(timer.elapsed() >= Duration::from_millis(195));
+
+        let _ = fs::remove_file(&path);
+    }
+}
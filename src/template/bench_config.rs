@@ -0,0 +1,203 @@
+/// Settings that control how long and how often a part is benched.
+use std::{collections::HashMap, env, fmt::Display, time::Duration};
+
+use tinyjson::JsonValue;
+
+pub const BUDGET_ENV: &str = "AOC_BENCH_BUDGET_MS";
+pub const MIN_SAMPLES_ENV: &str = "AOC_BENCH_MIN_SAMPLES";
+pub const MAX_SAMPLES_ENV: &str = "AOC_BENCH_MAX_SAMPLES";
+pub const WARMUP_ENV: &str = "AOC_BENCH_WARMUP";
+
+/// Settings used when benching a part.
+///
+/// The runner estimates how many samples fit into `budget` based on the first run of a part,
+/// then clamps that number to `min_samples..=max_samples`. `warmup` runs are discarded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BenchConfig {
+    pub budget: Duration,
+    pub min_samples: u64,
+    pub max_samples: u64,
+    pub warmup: u64,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        Self {
+            budget: Duration::from_secs(1),
+            min_samples: 10,
+            max_samples: 10_000,
+            warmup: 0,
+        }
+    }
+}
+
+impl BenchConfig {
+    /// Read the settings from the `AOC_BENCH_*` environment variables, falling back to the defaults.
+    pub fn from_env() -> Result<Self, String> {
+        let defaults = Self::default();
+
+        let config = Self {
+            budget: read_env(BUDGET_ENV)?.map_or(defaults.budget, Duration::from_millis),
+            min_samples: read_env(MIN_SAMPLES_ENV)?.unwrap_or(defaults.min_samples),
+            max_samples: read_env(MAX_SAMPLES_ENV)?.unwrap_or(defaults.max_samples),
+            warmup: read_env(WARMUP_ENV)?.unwrap_or(defaults.warmup),
+        };
+
+        config.validate()
+    }
+
+    /// Ensure that the sample range is not empty.
+    pub fn validate(self) -> Result<Self, String> {
+        if self.min_samples == 0 || self.min_samples > self.max_samples {
+            return Err(format!(
+                "invalid sample range {}..={}: expected 1 <= min samples <= max samples.",
+                self.min_samples, self.max_samples
+            ));
+        }
+        Ok(self)
+    }
+
+    /// Number of samples to collect for a part whose first run took `base_time`.
+    pub fn iterations(&self, base_time: &Duration) -> u64 {
+        let estimate = self.budget.as_nanos() / base_time.as_nanos().max(10);
+        u64::try_from(estimate)
+            .unwrap_or(u64::MAX)
+            .clamp(self.min_samples, self.max_samples)
+    }
+
+    /// Environment variables that pass these settings on to a child process.
+    pub fn env_vars(&self) -> Vec<(&'static str, String)> {
+        vec![
+            (BUDGET_ENV, self.budget.as_millis().to_string()),
+            (MIN_SAMPLES_ENV, self.min_samples.to_string()),
+            (MAX_SAMPLES_ENV, self.max_samples.to_string()),
+            (WARMUP_ENV, self.warmup.to_string()),
+        ]
+    }
+}
+
+fn read_env(key: &str) -> Result<Option<u64>, String> {
+    match env::var(key) {
+        Ok(x) => x
+            .parse()
+            .map(Some)
+            .map_err(|_| format!("expected `{key}` to be a positive integer, got `{x}`.")),
+        Err(_) => Ok(None),
+    }
+}
+
+impl Display for BenchConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "budget {:?}, {}..={} samples, {} warmup",
+            self.budget, self.min_samples, self.max_samples, self.warmup
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&BenchConfig> for JsonValue {
+    fn from(value: &BenchConfig) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        #[allow(clippy::cast_precision_loss)]
+        let mut insert = |key: &str, value: u128| {
+            map.insert(key.into(), JsonValue::Number(value as f64));
+        };
+
+        insert("budget_ms", value.budget.as_millis());
+        insert("min_samples", value.min_samples.into());
+        insert("max_samples", value.max_samples.into());
+        insert("warmup", value.warmup.into());
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for BenchConfig {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected bench config to be a JSON object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let get = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>())
+                .map(|v| *v as u64)
+                .ok_or(format!("Expected bench_config.{key} to be a number."))
+        };
+
+        Ok(BenchConfig {
+            budget: Duration::from_millis(get("budget_ms")?),
+            min_samples: get("min_samples")?,
+            max_samples: get("max_samples")?,
+            warmup: get("warmup")?,
+        })
+    }
+}
+
+/// Deserialize an optional config. Missing keys and `null` both map to `None`.
+pub fn from_json(value: Option<&JsonValue>) -> Result<Option<BenchConfig>, String> {
+    match value {
+        None => Ok(None),
+        Some(v) if v.is_null() => Ok(None),
+        Some(v) => BenchConfig::try_from(v).map(Some),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::BenchConfig;
+    use std::time::Duration;
+    use tinyjson::JsonValue;
+
+    #[test]
+    fn clamps_iterations() {
+        let config = BenchConfig::default();
+        assert_eq!(config.iterations(&Duration::from_secs(3)), 10);
+        assert_eq!(config.iterations(&Duration::from_millis(10)), 100);
+        assert_eq!(config.iterations(&Duration::from_nanos(1)), 10_000);
+    }
+
+    #[test]
+    fn respects_custom_budget() {
+        let config = BenchConfig {
+            budget: Duration::from_secs(10),
+            min_samples: 2,
+            max_samples: 50,
+            warmup: 1,
+        };
+        assert_eq!(config.iterations(&Duration::from_millis(2600)), 3);
+        assert_eq!(config.iterations(&Duration::from_secs(30)), 2);
+        assert_eq!(config.iterations(&Duration::from_millis(1)), 50);
+    }
+
+    #[test]
+    fn rejects_empty_sample_ranges() {
+        let config = BenchConfig {
+            min_samples: 100,
+            max_samples: 10,
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let config = BenchConfig {
+            budget: Duration::from_millis(250),
+            min_samples: 3,
+            max_samples: 30,
+            warmup: 2,
+        };
+        let json = JsonValue::from(&config);
+        assert_eq!(BenchConfig::try_from(&json).unwrap(), config);
+    }
+}
@@ -0,0 +1,194 @@
+/// The schedule of an event: how many puzzles it has and when they unlock.
+use std::env;
+
+#[cfg(feature = "today")]
+use chrono::{Datelike, FixedOffset, NaiveDate, Utc};
+
+use crate::template::{AllDays, Day, Year};
+
+/// Overrides the calendar of every year, e.g. `days=12,start=12-01,utc_offset=-5`.
+/// `AOC_CALENDAR_<year>` overrides the calendar of a single year.
+pub const CALENDAR_ENV: &str = "AOC_CALENDAR";
+
+/// The first year with fewer than 25 puzzles.
+const SHORT_EVENTS_SINCE: u16 = 2025;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Calendar {
+    /// Number of puzzles, at most 25.
+    pub days: u8,
+    /// Month and day of month on which the first puzzle unlocks.
+    pub start: (u32, u32),
+    /// Puzzles unlock at midnight in this offset from UTC, in hours.
+    pub utc_offset: i32,
+}
+
+/// The calendar of Advent of Code until 2024: 25 puzzles, unlocking from December 1st at midnight EST.
+impl Default for Calendar {
+    fn default() -> Self {
+        Self {
+            days: 25,
+            start: (12, 1),
+            utc_offset: -5,
+        }
+    }
+}
+
+impl Calendar {
+    /// The built-in calendar of `year`, overridden by `AOC_CALENDAR` and `AOC_CALENDAR_<year>`.
+    pub fn from_env(year: Option<Year>) -> Result<Self, String> {
+        let mut calendar = Self::default();
+
+        if year.is_some_and(|year| year.into_inner() >= SHORT_EVENTS_SINCE) {
+            calendar.days = 12;
+        }
+
+        let keys = [
+            Some(CALENDAR_ENV.to_string()),
+            year.map(|year| format!("{CALENDAR_ENV}_{year}")),
+        ];
+
+        for key in keys.iter().flatten() {
+            if let Ok(spec) = env::var(key) {
+                calendar = calendar.parse(&spec).map_err(|e| format!("{key}: {e}"))?;
+            }
+        }
+
+        Ok(calendar)
+    }
+
+    /// Apply the settings of a spec like `days=12,start=12-01,utc_offset=-5` on top of `self`.
+    /// Settings that are not part of the spec are kept.
+    pub fn parse(mut self, spec: &str) -> Result<Self, String> {
+        for setting in spec.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+            let invalid = || format!("invalid calendar setting `{setting}`.");
+            let (key, value) = setting.split_once('=').ok_or_else(invalid)?;
+
+            match key.trim() {
+                "days" => {
+                    self.days = value
+                        .trim()
+                        .parse()
+                        .ok()
+                        .filter(|days| (1..=25).contains(days))
+                        .ok_or_else(|| {
+                            format!("expected `days` to be between 1 and 25, got `{value}`.")
+                        })?;
+                }
+                "start" => {
+                    self.start = value
+                        .trim()
+                        .split_once('-')
+                        .and_then(|(month, day)| Some((month.parse().ok()?, day.parse().ok()?)))
+                        .filter(|(month, day)| (1..=12).contains(month) && (1..=31).contains(day))
+                        .ok_or_else(|| {
+                            format!("expected `start` to be a date like `12-01`, got `{value}`.")
+                        })?;
+                }
+                "utc_offset" => {
+                    self.utc_offset = value
+                        .trim()
+                        .parse()
+                        .ok()
+                        .filter(|offset| (-12..=14).contains(offset))
+                        .ok_or_else(|| {
+                            format!("expected `utc_offset` to be in hours, got `{value}`.")
+                        })?;
+                }
+                _ => return Err(invalid()),
+            }
+        }
+
+        Ok(self)
+    }
+
+    /// The last puzzle of the event.
+    pub fn last_day(&self) -> Day {
+        Day::__new_unchecked(self.days)
+    }
+
+    /// Whether `day` is part of the event.
+    pub fn contains(&self, day: Day) -> bool {
+        day <= self.days
+    }
+
+    /// An iterator that yields every day of the event.
+    pub fn days(&self) -> AllDays {
+        AllDays::until(self.last_day())
+    }
+}
+
+#[cfg(feature = "today")]
+impl Calendar {
+    /// Returns the puzzle that unlocked most recently if the event is running, `None` otherwise.
+    pub fn today(&self) -> Option<Day> {
+        let offset = FixedOffset::east_opt(self.utc_offset * 3600)?;
+        self.day_on(Utc::now().with_timezone(&offset).date_naive())
+    }
+
+    /// The puzzle that unlocks on `date`, if the event is running on that date.
+    fn day_on(&self, date: NaiveDate) -> Option<Day> {
+        // events that start in December may run into January of the following year.
+        [date.year(), date.year() - 1].into_iter().find_map(|year| {
+            let start = NaiveDate::from_ymd_opt(year, self.start.0, self.start.1)?;
+            let day = (date - start).num_days() + 1;
+            Day::new(u8::try_from(day).ok()?).filter(|day| self.contains(*day))
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Calendar;
+    use crate::{day, template::Day};
+
+    #[test]
+    fn parses_calendar_specs() {
+        let calendar = Calendar::default()
+            .parse("days=12, start=11-20,utc_offset=1")
+            .unwrap();
+        assert_eq!(calendar.days, 12);
+        assert_eq!(calendar.start, (11, 20));
+        assert_eq!(calendar.utc_offset, 1);
+
+        // settings that are not part of the spec are kept.
+        let calendar = Calendar::default().parse("days=20").unwrap();
+        assert_eq!(calendar.start, (12, 1));
+    }
+
+    #[test]
+    fn rejects_invalid_specs() {
+        assert!(Calendar::default().parse("days=26").is_err());
+        assert!(Calendar::default().parse("days=0").is_err());
+        assert!(Calendar::default().parse("start=13-01").is_err());
+        assert!(Calendar::default().parse("weeks=2").is_err());
+    }
+
+    #[test]
+    fn iterates_days_of_the_event() {
+        let calendar = Calendar::default().parse("days=12").unwrap();
+        assert_eq!(calendar.days().count(), 12);
+        assert_eq!(calendar.days().last(), Some(day!(12)));
+        assert!(calendar.contains(day!(12)));
+        assert!(!calendar.contains(day!(13)));
+        assert_eq!(Calendar::default().days().collect::<Vec<Day>>().len(), 25);
+    }
+
+    #[cfg(feature = "today")]
+    #[test]
+    fn finds_days_across_new_year() {
+        use chrono::NaiveDate;
+
+        let calendar = Calendar::default().parse("start=12-20").unwrap();
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+
+        assert_eq!(calendar.day_on(date(2024, 12, 19)), None);
+        assert_eq!(calendar.day_on(date(2024, 12, 20)), Some(day!(1)));
+        assert_eq!(calendar.day_on(date(2025, 1, 2)), Some(day!(14)));
+        assert_eq!(calendar.day_on(date(2025, 1, 13)), Some(day!(25)));
+        assert_eq!(calendar.day_on(date(2025, 1, 14)), None);
+        assert_eq!(Calendar::default().day_on(date(2025, 1, 2)), None);
+    }
+}
@@ -1,14 +1,44 @@
-use crate::template::{aoc_cli, Day};
-use std::process;
+use crate::template::{aoc_client::Client, Day, Workspace};
+use std::{fs, path::Path, process};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+/// Download the input and puzzle of a day.
+/// An existing, non-empty input is kept unless `force` is set.
+pub fn handle(workspace: Workspace, day: Day, force: bool) {
+    let input_path = workspace.data_path("inputs", &format!("{day}.txt"));
+    let puzzle_path = workspace.data_path("puzzles", &format!("{day}.md"));
+
+    let has_input = fs::metadata(&input_path).is_ok_and(|m| m.len() > 0);
+    let skip_input = has_input && !force;
+
+    let result = Client::from_env(workspace.year).and_then(|client| {
+        if !skip_input {
+            let input = client.input(day)?;
+            write_file(&input_path, &input)?;
+        }
+        let puzzle = client.puzzle(day)?;
+        write_file(&puzzle_path, &puzzle)?;
+        Ok(())
+    });
+
+    if let Err(e) = result {
+        eprintln!("Failed to download day {day}: {e}");
         process::exit(1);
     }
 
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
-    };
+    let (input_path, puzzle_path) = (input_path.display(), puzzle_path.display());
+
+    if skip_input {
+        println!("🎄 Input \"{input_path}\" already exists, skipping. Use `--force` to download it again.");
+    } else {
+        println!("🎄 Successfully wrote input to \"{input_path}\".");
+    }
+    println!("🎄 Successfully wrote puzzle to \"{puzzle_path}\".");
+}
+
+/// Write a file, creating its folder first. Folders of years namespaced by year may not exist yet.
+pub fn write_file(path: &Path, contents: &str) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, contents)
 }
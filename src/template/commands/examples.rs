@@ -0,0 +1,325 @@
+use std::{
+    fs,
+    io::{self, BufRead, Write},
+    path::PathBuf,
+    process,
+};
+
+use crate::template::{commands::download::write_file, puzzle, Day, Workspace};
+
+/// A file that the command proposes to write.
+struct Change {
+    path: PathBuf,
+    contents: String,
+    /// The lines of the file that change, as `(old, new)`.
+    lines: Vec<(String, String)>,
+}
+
+/// Propose the example files and test expectations of a day from its downloaded puzzle description.
+/// Changes are written after confirming them, or right away with `yes`.
+pub fn handle(workspace: Workspace, day: Day, yes: bool) {
+    let puzzle_path = workspace.data_path("puzzles", &format!("{day}.md"));
+
+    let Ok(markdown) = fs::read_to_string(&puzzle_path) else {
+        eprintln!(
+            "Puzzle \"{}\" not found. Download it with `cargo download {day}` first.",
+            puzzle_path.display()
+        );
+        process::exit(1);
+    };
+
+    let examples = puzzle::examples(&markdown);
+    let mut changes = vec![];
+
+    let part_one_input = examples.first().and_then(|e| e.input.as_deref());
+    // part two only needs its own example file if its example differs from the one of part one.
+    let part_two_input = examples
+        .get(1)
+        .and_then(|e| e.input.as_deref())
+        .filter(|input| Some(*input) != part_one_input);
+
+    for (input, file_name) in [
+        (part_one_input, format!("{day}.txt")),
+        (part_two_input, format!("{day}-2.txt")),
+    ] {
+        let Some(input) = input else {
+            continue;
+        };
+        let path = workspace.data_path("examples", &file_name);
+        let existing = fs::read_to_string(&path).unwrap_or_default();
+        if existing != input {
+            changes.push(Change {
+                path,
+                contents: input.to_string(),
+                lines: diff_lines(&existing, input),
+            });
+        }
+    }
+
+    let module_path = PathBuf::from(workspace.bin_path(day));
+    if let Ok(source) = fs::read_to_string(&module_path) {
+        let mut patched = source.clone();
+
+        for (i, example) in examples.iter().enumerate() {
+            let part = i as u8 + 1;
+            let example_part = (part == 2 && part_two_input.is_some()).then_some(2);
+            if let Some(p) = patch_test(&patched, part, example.answer.as_deref(), example_part) {
+                patched = p;
+            }
+        }
+
+        if patched != source {
+            changes.push(Change {
+                lines: diff_lines(&source, &patched),
+                path: module_path,
+                contents: patched,
+            });
+        }
+    }
+
+    if changes.is_empty() {
+        println!("🎄 Examples of day {day} are up to date.");
+        return;
+    }
+
+    for change in &changes {
+        println!("{}:", change.path.display());
+        for (old, new) in &change.lines {
+            if !old.is_empty() {
+                println!("- {old}");
+            }
+            println!("+ {new}");
+        }
+        println!();
+    }
+
+    if !yes && !confirm("Apply these changes?") {
+        println!("No files were changed.");
+        return;
+    }
+
+    for change in changes {
+        if let Err(e) = write_file(&change.path, &change.contents) {
+            eprintln!("Failed to write \"{}\": {e}", change.path.display());
+            process::exit(1);
+        }
+        println!("Updated \"{}\"", change.path.display());
+    }
+}
+
+fn confirm(question: &str) -> bool {
+    print!("{question} [y/N] ");
+    let _ = io::stdout().flush();
+
+    let mut answer = String::new();
+    if io::stdin().lock().read_line(&mut answer).is_err() {
+        return false;
+    }
+    matches!(answer.trim().to_lowercase().as_str(), "y" | "yes")
+}
+
+/// Lines that differ between two versions of a file. Lines missing from the old version are empty.
+fn diff_lines(old: &str, new: &str) -> Vec<(String, String)> {
+    let old: Vec<&str> = old.lines().collect();
+
+    new.lines()
+        .enumerate()
+        .filter(|(i, line)| old.get(*i) != Some(line))
+        .map(|(i, line)| (old.get(i).unwrap_or(&"").to_string(), line.to_string()))
+        .collect()
+}
+
+/// How a part returns its answer, derived from its return type.
+#[derive(Debug, PartialEq, Eq)]
+enum AnswerKind<'a> {
+    Plain(Option<&'a str>),
+    Option(Option<&'a str>),
+    Result(Option<&'a str>),
+}
+
+impl<'a> AnswerKind<'a> {
+    /// Read the return type of the function `name`, e.g. `Option<u32>` for `fn part_one(..) -> Option<u32>`.
+    fn of_fn(source: &'a str, name: &str) -> Option<Self> {
+        let start = source.find(&format!("fn {name}("))?;
+        let signature = &source[start..start + source[start..].find('{')?];
+        let (_, return_type) = signature.rsplit_once("->")?;
+        let return_type = return_type.trim();
+
+        let answer = if let Some(inner) = generic_argument(return_type, "Option<") {
+            AnswerKind::Option(Some(inner))
+        } else if let Some(inner) = generic_argument(return_type, "Result<") {
+            AnswerKind::Result(inner.split(',').next().map(str::trim))
+        } else {
+            AnswerKind::Plain(Some(return_type))
+        };
+        Some(answer)
+    }
+
+    /// Guess how a part returns its answer from the expectation of its test, e.g. `Some(143)`.
+    fn of_expected(expected: &str) -> Self {
+        if expected.starts_with("Some(") || expected == "None" {
+            AnswerKind::Option(None)
+        } else if expected.starts_with("Ok(") {
+            AnswerKind::Result(None)
+        } else {
+            AnswerKind::Plain(None)
+        }
+    }
+
+    /// The actual and expected value of an assertion that `actual` of this type equals `answer`.
+    fn assertion(&self, actual: &str, answer: &str) -> (String, String) {
+        match self {
+            AnswerKind::Plain(ty) => (actual.into(), literal(answer, *ty)),
+            AnswerKind::Option(ty) => (actual.into(), format!("Some({})", literal(answer, *ty))),
+            // errors do not need to be comparable, only the answers are compared.
+            AnswerKind::Result(ty) if actual.ends_with(".unwrap()") => {
+                (actual.into(), literal(answer, *ty))
+            }
+            AnswerKind::Result(ty) if actual.ends_with(".ok()") => {
+                (actual.into(), format!("Some({})", literal(answer, *ty)))
+            }
+            AnswerKind::Result(ty) => (
+                format!("{actual}.ok()"),
+                format!("Some({})", literal(answer, *ty)),
+            ),
+        }
+    }
+}
+
+/// The argument of a generic type, e.g. `u32` for `Option<u32>` and prefix `Option<`.
+fn generic_argument<'a>(ty: &'a str, prefix: &str) -> Option<&'a str> {
+    ty.strip_prefix(prefix)?.strip_suffix('>').map(str::trim)
+}
+
+/// A Rust literal of `answer` for the answer type `ty`, guessed from the answer if the type is not known.
+fn literal(answer: &str, ty: Option<&str>) -> String {
+    let mut chars = answer.chars();
+
+    match (ty, chars.next(), chars.next()) {
+        (Some("f32" | "f64"), ..) if answer.parse::<f64>().is_ok() && !answer.contains('.') => {
+            format!("{answer}.0")
+        }
+        (Some("f32" | "f64" | "bool"), ..) => answer.into(),
+        (Some("char"), Some(c), None) => format!("{c:?}"),
+        (Some("String"), ..) => format!("{answer:?}.to_string()"),
+        (Some(ty), ..) if ty.starts_with('&') => format!("{answer:?}"),
+        _ if answer.parse::<i64>().is_ok() => answer.into(),
+        _ => format!("{answer:?}.to_string()"),
+    }
+}
+
+/// Set the expectation of the test of `part` to `answer` and point it at the example file
+/// `example_part`, e.g. `02-2.txt` for 2. Returns `None` if the solution has no such test.
+/// The expectation matches the return type of the part, e.g. `Some(143)` for `Option<u32>`.
+fn patch_test(
+    source: &str,
+    part: u8,
+    answer: Option<&str>,
+    example_part: Option<u8>,
+) -> Option<String> {
+    let (name, part_fn) = match part {
+        1 => ("fn test_part_one()", "part_one"),
+        2 => ("fn test_part_two()", "part_two"),
+        _ => return None,
+    };
+
+    let start = source.find(name)?;
+    // the test ends where the next function starts.
+    let end = source[start + name.len()..]
+        .find("fn ")
+        .map_or(source.len(), |i| start + name.len() + i);
+
+    let mut test = source[start..end].to_string();
+
+    if let Some(answer) = answer {
+        let args_start = test.find("assert_eq!(")? + "assert_eq!(".len();
+        let args_end = args_start + test[args_start..].find(");")?;
+        let (actual, expected) = test[args_start..args_end].split_once(", ")?;
+
+        let kind = AnswerKind::of_fn(&source[..start], part_fn)
+            .unwrap_or_else(|| AnswerKind::of_expected(expected.trim()));
+        let (actual, expected) = kind.assertion(actual.trim(), answer);
+
+        test.replace_range(args_start..args_end, &format!("{actual}, {expected}"));
+    }
+
+    if let Some(n) = example_part {
+        test = test.replace(
+            "read_file(\"examples\", DAY)",
+            &format!("read_file_part(\"examples\", DAY, {n})"),
+        );
+    }
+
+    Some(format!("{}{test}{}", &source[..start], &source[end..]))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{literal, patch_test};
+
+    const SOURCE: &str = r#"#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&WORKSPACE.read_file("examples", DAY));
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&WORKSPACE.read_file("examples", DAY));
+        assert_eq!(result, None);
+    }
+}
+"#;
+
+    #[test]
+    fn patches_test_expectations() {
+        let patched = patch_test(SOURCE, 1, Some("143"), None).unwrap();
+        let patched = patch_test(&patched, 2, Some("ABC"), Some(2)).unwrap();
+
+        assert!(patched.contains(
+            "part_one(&WORKSPACE.read_file(\"examples\", DAY));\n        assert_eq!(result, Some(143));"
+        ));
+        assert!(patched.contains(
+            "part_two(&WORKSPACE.read_file_part(\"examples\", DAY, 2));\n        assert_eq!(result, Some(\"ABC\".to_string()));"
+        ));
+    }
+
+    #[test]
+    fn matches_return_types() {
+        let source = format!(
+            "pub fn part_one(input: &str) -> u64 {{\n    0\n}}\n\n\
+            pub fn part_two(input: &str) -> Result<String, Box<dyn Error>> {{\n    todo!()\n}}\n\n{SOURCE}"
+        );
+
+        let patched = patch_test(&source, 1, Some("143"), None).unwrap();
+        let patched = patch_test(&patched, 2, Some("ABC"), None).unwrap();
+        assert!(patched.contains("assert_eq!(result, 143);"));
+        assert!(patched.contains("assert_eq!(result.ok(), Some(\"ABC\".to_string()));"));
+
+        // patching again keeps the assertion intact.
+        let repatched = patch_test(&patched, 2, Some("XYZ"), None).unwrap();
+        assert!(repatched.contains("assert_eq!(result.ok(), Some(\"XYZ\".to_string()));"));
+    }
+
+    #[test]
+    fn formats_literals_of_answer_types() {
+        assert_eq!(literal("3", Some("f64")), "3.0");
+        assert_eq!(literal("2.5", Some("f32")), "2.5");
+        assert_eq!(literal("true", Some("bool")), "true");
+        assert_eq!(literal("x", Some("char")), "'x'");
+        assert_eq!(literal("12", Some("String")), "\"12\".to_string()");
+        assert_eq!(literal("ABC", Some("&'static str")), "\"ABC\"");
+        assert_eq!(literal("-4", None), "-4");
+    }
+
+    #[test]
+    fn skips_missing_tests() {
+        assert_eq!(patch_test("fn main() {}", 1, Some("1"), None), None);
+    }
+}
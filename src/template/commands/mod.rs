@@ -1,6 +1,11 @@
 pub mod all;
 pub mod download;
+pub mod examples;
 pub mod read;
+pub mod report;
 pub mod scaffold;
 pub mod solve;
+pub mod stars;
 pub mod time;
+pub mod verify;
+pub mod watch;
@@ -1,15 +1,77 @@
-use std::process;
+use std::{
+    env, fs,
+    io::{self, IsTerminal},
+    process,
+};
 
-use crate::template::{aoc_cli, Day};
+use crate::template::{
+    aoc_client::Client,
+    commands::download::write_file,
+    puzzle,
+    puzzle_reader::{self, Style},
+    Day, Workspace,
+};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
-    }
+/// Width that text is wrapped at if the terminal does not set `COLUMNS`.
+const DEFAULT_WIDTH: usize = 80;
 
-    if let Err(e) = aoc_cli::read(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
+/// Render the puzzle description of a day, or only of `part`.
+/// Reads the downloaded description and only downloads it if it does not exist yet.
+pub fn handle(workspace: Workspace, day: Day, part: Option<u8>, plain: bool) {
+    let puzzle_path = workspace.data_path("puzzles", &format!("{day}.md"));
+
+    let markdown = match fs::read_to_string(&puzzle_path) {
+        Ok(markdown) => markdown,
+        Err(_) => {
+            let puzzle =
+                match Client::from_env(workspace.year).and_then(|client| client.puzzle(day)) {
+                    Ok(puzzle) => puzzle,
+                    Err(e) => {
+                        eprintln!("Failed to read day {day}: {e}");
+                        process::exit(1);
+                    }
+                };
+
+            if let Err(e) = write_file(&puzzle_path, &puzzle) {
+                eprintln!(
+                    "Failed to write puzzle to \"{}\": {e}",
+                    puzzle_path.display()
+                );
+            }
+
+            puzzle
+        }
+    };
+
+    let markdown = match part {
+        None => markdown.as_str(),
+        Some(part @ (1 | 2)) => match puzzle::parts(&markdown).get(usize::from(part) - 1) {
+            Some(description) => description,
+            None => {
+                eprintln!(
+                    "Part {part} of day {day} is not available yet. \
+                    Once part one is solved, update the puzzle with `cargo download {day}`."
+                );
+                process::exit(1);
+            }
+        },
+        Some(part) => {
+            eprintln!("Puzzles only have parts 1 and 2, not {part}.");
+            process::exit(1);
+        }
     };
+
+    // escape codes get in the way of pagers and files.
+    let style = if plain || !io::stdout().is_terminal() || env::var_os("NO_COLOR").is_some() {
+        Style::Plain
+    } else {
+        Style::Color
+    };
+
+    let width = env::var("COLUMNS")
+        .ok()
+        .and_then(|columns| columns.parse().ok())
+        .unwrap_or(DEFAULT_WIDTH);
+
+    print!("{}", puzzle_reader::render(markdown, style, width));
 }
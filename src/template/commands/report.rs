@@ -0,0 +1,31 @@
+use std::{path::PathBuf, process};
+
+use crate::template::readme_benchmarks::Metric;
+use crate::template::report::{self, Format, Target};
+use crate::template::timings::Timings;
+
+/// Render the stored timings of all years. The format defaults to the extension of `output`,
+/// or Markdown if there is none.
+pub fn handle(format: Option<Format>, output: Option<PathBuf>, readme: bool, metric: Metric) {
+    let target = match output {
+        _ if readme => Target::Readme,
+        Some(path) => Target::File(path),
+        None => Target::Stdout,
+    };
+
+    let format = format
+        .or_else(|| match &target {
+            Target::File(path) => Format::from_path(path),
+            _ => None,
+        })
+        .unwrap_or_default();
+
+    if let Err(e) = report::write(Timings::read_from_file(), format, metric, &target) {
+        eprintln!("Failed to write report: {e}");
+        process::exit(1);
+    }
+
+    if let Target::File(path) = target {
+        eprintln!("🎄 Successfully wrote report to \"{}\".", path.display());
+    }
+}
@@ -1,15 +1,17 @@
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::Write,
+    path::Path,
     process,
 };
 
-use crate::template::Day;
-
-const MODULE_TEMPLATE: &str =
-    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
+use crate::template::{
+    aoc_client::puzzle_title,
+    templates::{Placeholders, Template},
+    Day, Workspace,
+};
 
-fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
+fn safe_create_file(path: &Path, overwrite: bool) -> Result<File, std::io::Error> {
     let mut file = OpenOptions::new();
     if overwrite {
         file.create(true);
@@ -19,20 +21,55 @@ fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error>
     file.truncate(true).write(true).open(path)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
+/// Create an empty data file. Returns `false` if the file already has contents, which are kept,
+/// e.g. an input that `scaffold --download` downloaded before.
+fn create_file(path: &Path) -> Result<bool, std::io::Error> {
+    if fs::metadata(path).is_ok_and(|m| m.len() > 0) {
+        return Ok(false);
+    }
+
+    // the data folders of a year namespaced by year may not exist yet.
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
     OpenOptions::new()
         .write(true)
         .create(true)
         .truncate(true)
-        .open(path)
+        .open(path)?;
+    Ok(true)
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+/// Create the module, input and example files of a day from `template`, or the default template if `None`.
+pub fn handle(workspace: Workspace, day: Day, overwrite: bool, template: Option<&str>) {
+    let template = match Template::load(template) {
+        Ok(template) => template,
+        Err(e) => {
+            eprintln!("Failed to load template: {e}");
+            process::exit(1);
+        }
+    };
+
+    // the title is only known if the puzzle was downloaded before.
+    let puzzle = fs::read_to_string(workspace.data_path("puzzles", &format!("{day}.md")));
+    let title = puzzle
+        .as_deref()
+        .ok()
+        .and_then(puzzle_title)
+        .map_or_else(|| format!("Day {}", day.into_inner()), str::to_string);
+
+    let input_path = workspace.data_path("inputs", &format!("{day}.txt"));
+    let example_paths: Vec<_> = (1..=template.examples)
+        .map(|n| match n {
+            1 => workspace.data_path("examples", &format!("{day}.txt")),
+            n => workspace.data_path("examples", &format!("{day}-{n}.txt")),
+        })
+        .collect();
+    let module_path = workspace.bin_path(day);
+    let module_path = Path::new(&module_path);
 
-    let mut file = match safe_create_file(&module_path, overwrite) {
+    let mut file = match safe_create_file(module_path, overwrite) {
         Ok(file) => file,
         Err(e) => {
             eprintln!("Failed to create module file: {e}");
@@ -40,13 +77,15 @@ pub fn handle(day: Day, overwrite: bool) {
         }
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
+    let contents = template.render(&Placeholders {
+        day,
+        year: workspace.year,
+        title: &title,
+    });
+
+    match file.write_all(contents.as_bytes()) {
         Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
+            println!("Created module file \"{}\"", module_path.display());
         }
         Err(e) => {
             eprintln!("Failed to write module contents: {e}");
@@ -55,8 +94,11 @@ pub fn handle(day: Day, overwrite: bool) {
     }
 
     match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
+        Ok(true) => {
+            println!("Created empty input file \"{}\"", input_path.display());
+        }
+        Ok(false) => {
+            println!("Kept existing input file \"{}\"", input_path.display());
         }
         Err(e) => {
             eprintln!("Failed to create input file: {e}");
@@ -64,16 +106,24 @@ pub fn handle(day: Day, overwrite: bool) {
         }
     }
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
-            process::exit(1);
+    for example_path in example_paths {
+        match create_file(&example_path) {
+            Ok(true) => {
+                println!("Created empty example file \"{}\"", example_path.display());
+            }
+            Ok(false) => {
+                println!("Kept existing example file \"{}\"", example_path.display());
+            }
+            Err(e) => {
+                eprintln!("Failed to create example file: {e}");
+                process::exit(1);
+            }
         }
     }
 
     println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    match workspace.year.filter(|_| workspace.is_namespaced()) {
+        Some(year) => println!("🎄 Type `cargo solve {day} --year {year}` to run your solution."),
+        None => println!("🎄 Type `cargo solve {day}` to run your solution."),
+    }
 }
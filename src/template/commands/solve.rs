@@ -1,9 +1,15 @@
-use std::process::{Command, Stdio};
+use std::process::{self, Command, Stdio};
 
-use crate::template::Day;
+use crate::template::{input::Source, run_multi::RunOptions, Day};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+/// Run the solution of a single day against `input`. Of `options`, only release, timing, benchmark,
+/// timeout, heap and workspace settings apply.
+pub fn handle(day: Day, dhat: bool, submit_part: Option<u8>, input: &Source, options: &RunOptions) {
+    let mut cmd_args = vec![
+        "run".to_string(),
+        "--bin".to_string(),
+        options.workspace.bin_name(day),
+    ];
 
     if dhat {
         cmd_args.extend([
@@ -12,23 +18,40 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
             "--features".to_string(),
             "dhat-heap".to_string(),
         ]);
-    } else if release {
-        cmd_args.push("--release".to_string());
+    } else {
+        if options.is_release {
+            cmd_args.push("--release".to_string());
+        }
+        if options.heap {
+            cmd_args.extend(["--features".to_string(), "heap-stats".to_string()]);
+        }
     }
 
     cmd_args.push("--".to_string());
+    cmd_args.extend(input.args());
 
     if let Some(submit_part) = submit_part {
         cmd_args.push("--submit".to_string());
         cmd_args.push(submit_part.to_string());
     }
 
+    if options.is_timed {
+        cmd_args.push("--time".to_string());
+    }
+
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
+        .envs(options.bench.env_vars())
+        .envs(options.timeouts.env_vars())
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
         .spawn()
         .unwrap();
 
-    cmd.wait().unwrap();
+    let status = cmd.wait().unwrap();
+
+    // forward failures, e.g. a result that regressed from its recorded answer or a timeout.
+    if !status.success() {
+        process::exit(status.code().unwrap_or(1));
+    }
 }
@@ -0,0 +1,14 @@
+use crate::template::calendar::Calendar;
+use crate::template::readme_stars;
+use crate::template::timings::Timings;
+use crate::template::Workspace;
+
+pub fn handle(workspace: Workspace, calendar: Calendar) {
+    match readme_stars::update(&workspace, &calendar, &Timings::read_from_file()) {
+        Ok(()) => println!("Stored updated stars."),
+        Err(e) => {
+            eprintln!("Failed to store updated stars: {e:?}");
+            std::process::exit(1);
+        }
+    }
+}
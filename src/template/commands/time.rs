@@ -1,34 +1,88 @@
 use std::collections::HashSet;
 
-use crate::template::run_multi::run_multi;
+use crate::template::history::{self, HistoryEntry};
+use crate::template::readme_benchmarks::Metric;
+use crate::template::readme_stars;
+use crate::template::report::{self, Format, Target};
+use crate::template::results::part_label;
+use crate::template::run_multi::{run_multi, RunOptions};
+use crate::template::stats::format_nanos;
 use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::{Day, Year, ANSI_BOLD, ANSI_RESET};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
-    let stored_timings = Timings::read_from_file();
+pub fn handle(
+    day: Option<Day>,
+    run_all: bool,
+    store: bool,
+    metric: Metric,
+    compare_threshold: Option<f64>,
+    options: RunOptions,
+) {
+    let all_stored_timings = Timings::read_from_file();
+    let stored_timings = all_stored_timings.for_year(options.workspace.year);
 
     let days_to_run = day.map_or_else(
         || {
             if run_all {
-                all_days().collect()
+                options.calendar.days().collect()
+            } else if compare_threshold.is_some() {
+                // when comparing, run every day that has stored timings.
+                stored_timings.data.iter().map(|t| t.day).collect()
             } else {
                 // when the `--all` flag is not set, filter out days that are fully benched.
-                all_days()
-                    .filter(|day| !stored_timings.is_day_complete(*day))
+                options
+                    .calendar
+                    .days()
+                    .filter(|day| !stored_timings.is_day_complete(options.workspace.year, *day))
                     .collect()
             }
         },
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let options = RunOptions {
+        is_release: true,
+        is_timed: true,
+        // NOTE: benchmarks always run serially so that they do not interfere with each other.
+        jobs: 1,
+        ..options
+    };
+
+    let summary = run_multi(&days_to_run, &options);
+    let timings = summary.timings.clone().unwrap();
+
+    let is_slower = compare_threshold.is_some_and(|threshold| {
+        compare(
+            &stored_timings,
+            &timings,
+            metric == Metric::Median,
+            threshold,
+        )
+    });
 
     if store {
-        let merged_timings = stored_timings.merge(&timings);
+        let merged_timings = all_stored_timings.merge(&timings);
         merged_timings.store_file().unwrap();
 
+        if let Err(e) = history::append(&HistoryEntry::new(
+            options.workspace.year,
+            timings,
+            options.is_release,
+        )) {
+            eprintln!("Failed to append to timing history: {e}");
+        }
+
         println!();
-        match readme_benchmarks::update(merged_timings) {
+        match readme_stars::update(&options.workspace, &options.calendar, &merged_timings) {
+            Ok(()) => {
+                println!("Stored updated stars.");
+            }
+            Err(_) => {
+                eprintln!("Failed to store updated stars.");
+            }
+        }
+
+        match report::write(merged_timings, Format::Markdown, metric, &Target::Readme) {
             Ok(()) => {
                 println!("Stored updated benchmarks.");
             }
@@ -37,4 +91,107 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
             }
         }
     }
+
+    summary.exit_on_regression();
+
+    if is_slower {
+        std::process::exit(1);
+    }
+}
+
+/// Print how the timings of a day evolved across stored runs.
+pub fn history(year: Option<Year>, day: Day) {
+    let entries = match history::read_from_file() {
+        Ok(entries) => entries,
+        Err(e) => {
+            eprintln!("Failed to read timing history: {e}");
+            std::process::exit(1);
+        }
+    };
+
+    println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+    println!("------");
+
+    // only solutions with a shared `parse` stage have a parse column.
+    let has_parse = entries
+        .iter()
+        .filter_map(|entry| entry.timing(year, day))
+        .any(|timing| timing.parse.is_some());
+
+    let mut previous: Option<f64> = None;
+    let mut count = 0;
+
+    for entry in &entries {
+        let Some(timing) = entry.timing(year, day) else {
+            continue;
+        };
+        count += 1;
+
+        let trend = match previous {
+            Some(previous) if previous > 0.0 => {
+                format!(
+                    " ({:+.1}%)",
+                    (timing.total_nanos - previous) / previous * 100.0
+                )
+            }
+            _ => String::new(),
+        };
+        previous = Some(timing.total_nanos);
+
+        println!(
+            "{}  {:<8} {:<8} {:<12} {} Total: {}{trend}",
+            history::format_timestamp(entry.timestamp),
+            entry.commit.as_deref().unwrap_or("-"),
+            entry.profile,
+            entry.machine,
+            history::format_parts(timing, has_parse),
+            format_nanos(timing.total_nanos),
+        );
+    }
+
+    if count == 0 {
+        println!("No recorded timings. Run `cargo time {day} --store` to record some.");
+    }
+}
+
+/// Print the change of each part compared to the stored timings.
+/// Returns `true` if any part got slower by more than `threshold` percent.
+fn compare(stored: &Timings, timings: &Timings, median: bool, threshold: f64) -> bool {
+    let deltas = stored.compare(timings, median);
+
+    println!("\n{ANSI_BOLD}Comparison with stored timings{ANSI_RESET}");
+    println!("------");
+
+    if deltas.is_empty() {
+        println!("No stored timings to compare with.");
+        return false;
+    }
+
+    let mut regressions = 0;
+
+    for delta in &deltas {
+        let percent = delta.percent();
+        let marker = if percent > threshold {
+            regressions += 1;
+            "✘"
+        } else if percent < -threshold {
+            "⚡"
+        } else {
+            " "
+        };
+
+        println!(
+            "{marker} Day {} {}: {} → {} ({percent:+.1}%)",
+            delta.day,
+            part_label(delta.part).to_lowercase(),
+            format_nanos(delta.old_nanos),
+            format_nanos(delta.new_nanos),
+        );
+    }
+
+    if regressions > 0 {
+        eprintln!("\n{regressions} part(s) got slower by more than {threshold}%.");
+    }
+
+    regressions > 0
 }
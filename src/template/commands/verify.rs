@@ -0,0 +1,67 @@
+use std::collections::HashSet;
+
+use crate::template::answers::Answers;
+use crate::template::results::{Status, PARSE_PART};
+use crate::template::run_multi::{run_multi, RunOptions};
+use crate::template::{calendar::Calendar, timeout::Timeouts, Day, Workspace};
+
+pub fn handle(
+    workspace: Workspace,
+    calendar: Calendar,
+    day: Option<Day>,
+    record: bool,
+    subprocess: bool,
+    timeouts: Timeouts,
+) {
+    let days_to_run = day.map_or_else(|| calendar.days().collect(), |day| HashSet::from([day]));
+
+    let options = RunOptions {
+        is_release: true,
+        subprocess,
+        timeouts,
+        workspace,
+        calendar,
+        ..Default::default()
+    };
+
+    let summary = run_multi(&days_to_run, &options);
+
+    println!();
+
+    if record {
+        let mut answers = Answers::read_from_file(&workspace);
+        let mut count = 0;
+
+        for result in &summary.results {
+            if let (Status::Solved, Some(answer)) = (result.status, &result.answer) {
+                answers.record(result.day, result.part, answer);
+                count += 1;
+            }
+        }
+
+        match answers.store_file(&workspace) {
+            Ok(()) => println!("Recorded {count} answer(s)."),
+            Err(e) => eprintln!("Failed to store answers: {e}"),
+        }
+        return;
+    }
+
+    let verified = summary
+        .results
+        .iter()
+        .filter(|r| r.verified == Some(true))
+        .count();
+
+    let unknown = summary
+        .results
+        .iter()
+        .filter(|r| r.status == Status::Solved && r.part != PARSE_PART && r.verified.is_none())
+        .count();
+
+    println!(
+        "{verified} part(s) verified, {} regressed, {unknown} without a recorded answer.",
+        summary.regressions().len()
+    );
+
+    summary.exit_on_regression();
+}
@@ -0,0 +1,194 @@
+use std::{
+    fs,
+    path::PathBuf,
+    process::{Command, Stdio},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::template::results::{PartResult, Status, PARSE_PART};
+use crate::template::run_multi::{child_commands, RunOptions};
+use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+/// How often the files of the day are checked for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Re-run the tests and the solution of a day whenever its source, examples or input change.
+/// Of `options`, only release, timeout and workspace settings apply.
+pub fn handle(day: Day, options: &RunOptions) {
+    println!(
+        "🎄 Watching day {day}, press Ctrl-C to stop. {ANSI_ITALIC}({}){ANSI_RESET}",
+        options.workspace.bin_path(day)
+    );
+
+    let mut previous: Option<Vec<PartResult>> = None;
+    let mut last_snapshot = None;
+
+    loop {
+        let snapshot = snapshot(day, options);
+
+        if last_snapshot.as_ref() != Some(&snapshot) {
+            last_snapshot = Some(snapshot);
+            previous = Some(run(day, options, previous.as_deref()));
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Run the example tests, then the real input, and print how the answers changed.
+fn run(day: Day, options: &RunOptions, previous: Option<&[PartResult]>) -> Vec<PartResult> {
+    println!("\n{ANSI_BOLD}Day {day}{ANSI_RESET}");
+    println!("------");
+
+    let bin_name = options.workspace.bin_name(day);
+    let mut args = vec!["test", "--quiet", "--bin", &bin_name];
+    if options.is_release {
+        args.push("--release");
+    }
+
+    let tests = Command::new("cargo")
+        .args(&args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output();
+
+    match tests {
+        Ok(output) if output.status.success() => println!("Tests: ✔ passed"),
+        Ok(output) => {
+            print!("{}", String::from_utf8_lossy(&output.stdout));
+            eprint!("{}", String::from_utf8_lossy(&output.stderr));
+            println!("Tests: ✘ failed");
+        }
+        Err(e) => eprintln!("Failed to run tests: {e}"),
+    }
+
+    let output = match child_commands::run_solution(day, options, true) {
+        Ok(Some(output)) => output,
+        Ok(None) => {
+            println!("Not solved.");
+            return vec![];
+        }
+        Err(e) => {
+            eprintln!("Failed to run solution: {e:?}");
+            return previous.map(<[PartResult]>::to_vec).unwrap_or_default();
+        }
+    };
+
+    output.stderr.iter().for_each(|line| eprintln!("{line}"));
+
+    diff_answers(previous, &output.results)
+        .iter()
+        .for_each(|line| println!("{line}"));
+
+    output.results
+}
+
+/// Summarize the answers of each part compared to the previous run, e.g. `Part 1: 42 → 43`.
+fn diff_answers(previous: Option<&[PartResult]>, current: &[PartResult]) -> Vec<String> {
+    let describe = |result: &PartResult| match result.status {
+        Status::Solved => result.answer.clone().unwrap_or_default(),
+        Status::Unsolved => "✖".into(),
+        Status::Timeout => "⏱ timeout".into(),
+        Status::Error => format!("error: {}", result.error.as_deref().unwrap_or_default()),
+    };
+
+    current
+        .iter()
+        .filter(|result| result.part != PARSE_PART)
+        .map(|result| {
+            let answer = describe(result);
+            let old = previous
+                .and_then(|p| p.iter().find(|r| r.part == result.part))
+                .map(describe);
+
+            match old {
+                Some(old) if old == answer => format!("Part {}: {answer} (unchanged)", result.part),
+                Some(old) => format!("Part {}: {old} → {answer}", result.part),
+                None => format!("Part {}: {answer}", result.part),
+            }
+        })
+        .collect()
+}
+
+/// Modification times of the solution, examples and input of a day.
+fn snapshot(day: Day, options: &RunOptions) -> Vec<(PathBuf, Option<SystemTime>)> {
+    let workspace = options.workspace;
+
+    let mut paths = vec![
+        PathBuf::from(workspace.bin_path(day)),
+        workspace.data_path("inputs", &format!("{day}.txt")),
+    ];
+
+    // examples of a day may be split into several files, e.g. `01.txt` and `01-2.txt`.
+    let examples = workspace.data_path("examples", "");
+    if let Ok(entries) = fs::read_dir(&examples) {
+        let mut files: Vec<PathBuf> = entries
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| {
+                        name == format!("{day}.txt")
+                            || (name.starts_with(&format!("{day}-")) && name.ends_with(".txt"))
+                    })
+            })
+            .collect();
+        files.sort();
+        paths.extend(files);
+    }
+
+    paths
+        .into_iter()
+        .map(|path| {
+            let modified = fs::metadata(&path).and_then(|m| m.modified()).ok();
+            (path, modified)
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::diff_answers;
+    use crate::{
+        day,
+        template::results::{PartResult, Status},
+    };
+
+    fn result(part: u8, status: Status, answer: Option<&str>) -> PartResult {
+        PartResult {
+            day: day!(1),
+            part,
+            status,
+            answer: answer.map(Into::into),
+            error: None,
+            nanos: 0.0,
+            samples: 1,
+            stats: None,
+            bench_config: None,
+            verified: None,
+            heap: None,
+        }
+    }
+
+    #[test]
+    fn diffs_answers_against_previous_run() {
+        let previous = [
+            result(1, Status::Solved, Some("42")),
+            result(2, Status::Unsolved, None),
+        ];
+        let current = [
+            result(1, Status::Solved, Some("42")),
+            result(2, Status::Solved, Some("7")),
+        ];
+
+        assert_eq!(
+            diff_answers(Some(&previous), &current),
+            vec!["Part 1: 42 (unchanged)", "Part 2: ✖ → 7"]
+        );
+        assert_eq!(diff_answers(None, &current[1..]), vec!["Part 2: 7"]);
+    }
+}
@@ -0,0 +1,33 @@
+/// The UTC calendar date `(year, month, day)` of a unix timestamp.
+/// Used to format timestamps and to find the year of the latest event, without a date library.
+pub fn civil_date(timestamp: u64) -> (i64, i64, i64) {
+    let days = timestamp / 86_400;
+
+    // civil-from-days, see: http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    #[allow(clippy::cast_possible_wrap)]
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    (year, month, day)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::civil_date;
+
+    #[test]
+    fn converts_timestamps_to_dates() {
+        assert_eq!(civil_date(0), (1970, 1, 1));
+        assert_eq!(civil_date(1_709_210_096), (2024, 2, 29));
+        assert_eq!(civil_date(1_735_689_599), (2024, 12, 31));
+    }
+}
@@ -0,0 +1,236 @@
+/// Heap allocation statistics, collected by a counting global allocator behind the `heap-stats` feature.
+use std::{collections::HashMap, fmt::Display};
+
+use tinyjson::JsonValue;
+
+/// Allocations made while running a part once.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct HeapStats {
+    /// Sum of the sizes of all allocations, in bytes.
+    pub total_bytes: u64,
+    /// Largest amount of memory held at once, in bytes, on top of what was allocated before the part started.
+    pub peak_bytes: u64,
+    /// Number of allocations, including reallocations.
+    pub allocations: u64,
+}
+
+#[cfg(feature = "heap-stats")]
+mod counting {
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    static TOTAL: AtomicU64 = AtomicU64::new(0);
+    static CURRENT: AtomicU64 = AtomicU64::new(0);
+    static PEAK: AtomicU64 = AtomicU64::new(0);
+    static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+
+    /// A global allocator that delegates to the system allocator and counts allocations.
+    pub struct CountingAlloc;
+
+    fn record_alloc(size: usize) {
+        let size = size as u64;
+        TOTAL.fetch_add(size, Ordering::Relaxed);
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        let current = CURRENT.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK.fetch_max(current, Ordering::Relaxed);
+    }
+
+    fn record_dealloc(size: usize) {
+        CURRENT.fetch_sub(size as u64, Ordering::Relaxed);
+    }
+
+    unsafe impl GlobalAlloc for CountingAlloc {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc(layout);
+            if !ptr.is_null() {
+                record_alloc(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc_zeroed(layout);
+            if !ptr.is_null() {
+                record_alloc(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout);
+            record_dealloc(layout.size());
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            let new_ptr = System.realloc(ptr, layout, new_size);
+            if !new_ptr.is_null() {
+                record_dealloc(layout.size());
+                record_alloc(new_size);
+            }
+            new_ptr
+        }
+    }
+
+    /// Snapshot of the counters, resetting the peak to the current heap size.
+    pub fn start() -> (u64, u64, u64) {
+        let current = CURRENT.load(Ordering::Relaxed);
+        PEAK.store(current, Ordering::Relaxed);
+        (
+            TOTAL.load(Ordering::Relaxed),
+            ALLOCATIONS.load(Ordering::Relaxed),
+            current,
+        )
+    }
+
+    pub fn finish((total, allocations, current): (u64, u64, u64)) -> super::HeapStats {
+        super::HeapStats {
+            total_bytes: TOTAL.load(Ordering::Relaxed) - total,
+            peak_bytes: PEAK.load(Ordering::Relaxed).saturating_sub(current),
+            allocations: ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+        }
+    }
+}
+
+#[cfg(feature = "heap-stats")]
+pub use counting::CountingAlloc;
+
+/// Run `func` and collect the allocations it made.
+/// Returns `None` for the stats if the counting allocator is not installed.
+pub fn measure<T>(func: impl FnOnce() -> T) -> (T, Option<HeapStats>) {
+    // NOTE: the `dhat-heap` feature installs its own global allocator instead.
+    #[cfg(all(feature = "heap-stats", not(feature = "dhat-heap")))]
+    {
+        let snapshot = counting::start();
+        let result = func();
+        (result, Some(counting::finish(snapshot)))
+    }
+
+    #[cfg(not(all(feature = "heap-stats", not(feature = "dhat-heap"))))]
+    (func(), None)
+}
+
+/// Format a number of bytes with a binary unit, e.g. `1.5 KiB`.
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+impl Display for HeapStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "heap {} total · {} peak · {} allocations",
+            format_bytes(self.total_bytes),
+            format_bytes(self.peak_bytes),
+            self.allocations
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&HeapStats> for JsonValue {
+    fn from(value: &HeapStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        #[allow(clippy::cast_precision_loss)]
+        let mut insert = |key: &str, value: u64| {
+            map.insert(key.into(), JsonValue::Number(value as f64));
+        };
+
+        insert("total_bytes", value.total_bytes);
+        insert("peak_bytes", value.peak_bytes);
+        insert("allocations", value.allocations);
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for HeapStats {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected heap stats to be a JSON object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let get = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>())
+                .map(|v| *v as u64)
+                .ok_or(format!("Expected heap.{key} to be a number."))
+        };
+
+        Ok(HeapStats {
+            total_bytes: get("total_bytes")?,
+            peak_bytes: get("peak_bytes")?,
+            allocations: get("allocations")?,
+        })
+    }
+}
+
+/// Serialize optional heap stats, mapping `None` to `null`.
+pub fn to_json(stats: Option<&HeapStats>) -> JsonValue {
+    stats.map_or(JsonValue::Null, JsonValue::from)
+}
+
+/// Deserialize optional heap stats. Missing keys and `null` both map to `None`.
+pub fn from_json(value: Option<&JsonValue>) -> Result<Option<HeapStats>, String> {
+    match value {
+        None => Ok(None),
+        Some(v) if v.is_null() => Ok(None),
+        Some(v) => HeapStats::try_from(v).map(Some),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_bytes, HeapStats};
+    use tinyjson::JsonValue;
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let stats = HeapStats {
+            total_bytes: 4096,
+            peak_bytes: 1024,
+            allocations: 12,
+        };
+        let json = JsonValue::from(&stats);
+        assert_eq!(HeapStats::try_from(&json).unwrap(), stats);
+    }
+
+    #[cfg(all(feature = "heap-stats", not(feature = "dhat-heap")))]
+    #[test]
+    fn measures_allocations() {
+        let (_, stats) = super::measure(|| vec![0_u8; 4096]);
+        let stats = stats.unwrap();
+        assert!(stats.total_bytes >= 4096);
+        assert!(stats.peak_bytes >= 4096);
+        assert!(stats.allocations >= 1);
+    }
+}
@@ -0,0 +1,328 @@
+/// Append-only history of stored timings, so that the evolution of a day's runtime is not lost
+/// when `data/timings.json` is overwritten.
+use std::{
+    collections::HashMap,
+    env,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    process::Command,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::{
+    date::civil_date,
+    timings::{Timing, Timings},
+    Day, Year,
+};
+
+static HISTORY_FILE_PATH: &str = "./data/timings_history.jsonl";
+
+/// A single benchmark run, stored as one line of JSON.
+#[derive(Clone, Debug)]
+pub struct HistoryEntry {
+    /// The year the timings belong to, as all years share the history file.
+    /// Absent in entries stored by older versions.
+    pub year: Option<Year>,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    /// Short hash of the git commit the run was made on, if available.
+    pub commit: Option<String>,
+    pub profile: String,
+    pub machine: String,
+    pub timings: Timings,
+}
+
+impl HistoryEntry {
+    /// Create an entry for timings that were just recorded, describing the current environment.
+    pub fn new(year: Option<Year>, mut timings: Timings, is_release: bool) -> Self {
+        timings.assign_year(year);
+        Self {
+            year,
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()),
+            commit: git_commit(),
+            profile: if is_release { "release" } else { "debug" }.into(),
+            machine: machine_label(),
+            timings,
+        }
+    }
+
+    /// The timing of a single day in this entry, if it was run.
+    pub fn timing(&self, year: Option<Year>, day: Day) -> Option<&Timing> {
+        self.timings
+            .data
+            .iter()
+            .find(|t| t.year == year && t.day == day)
+    }
+}
+
+/// Append an entry to the history file.
+pub fn append(entry: &HistoryEntry) -> Result<(), io::Error> {
+    let line = JsonValue::from(entry)
+        .stringify()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(HISTORY_FILE_PATH)?;
+
+    writeln!(file, "{line}")
+}
+
+/// Read all entries from the history file. If not present, returns an empty history.
+pub fn read_from_file() -> Result<Vec<HistoryEntry>, String> {
+    let Ok(s) = fs::read_to_string(HISTORY_FILE_PATH) else {
+        return Ok(vec![]);
+    };
+
+    let mut entries = parse(&s)?;
+    // entries of older versions only stored timings of the year configured in `AOC_YEAR`.
+    let env_year = Year::from_env();
+    entries
+        .iter_mut()
+        .for_each(|entry| entry.timings.assign_year(entry.year.or(env_year)));
+
+    Ok(entries)
+}
+
+fn parse(s: &str) -> Result<Vec<HistoryEntry>, String> {
+    s.lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| {
+            let json = JsonValue::from_str(l).or(Err("history entry is not valid JSON."))?;
+            HistoryEntry::try_from(&json)
+        })
+        .collect()
+}
+
+/// A label for the machine the benchmarks ran on.
+/// Uses `AOC_MACHINE` if set, the hostname otherwise.
+fn machine_label() -> String {
+    env::var("AOC_MACHINE")
+        .or_else(|_| env::var("HOSTNAME"))
+        .ok()
+        .or_else(|| fs::read_to_string("/etc/hostname").ok())
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| "unknown".into())
+}
+
+fn git_commit() -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Format the timings of the parts of a day as shown by `time --history`, including parsing if `with_parse`.
+pub fn format_parts(timing: &Timing, with_parse: bool) -> String {
+    let part = |label: &str, time: &Option<String>| {
+        format!("{label}: {:<10}", time.as_deref().unwrap_or("-"))
+    };
+
+    let mut parts = vec![];
+    if with_parse {
+        parts.push(part("Parse", &timing.parse));
+    }
+    parts.push(part("Part 1", &timing.part_1));
+    parts.push(part("Part 2", &timing.part_2));
+    parts.join(" ")
+}
+
+/// Format a unix timestamp as `YYYY-MM-DD HH:MM` (UTC).
+pub fn format_timestamp(timestamp: u64) -> String {
+    let (year, month, day) = civil_date(timestamp);
+    let minutes = (timestamp % 86_400) / 60;
+
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}",
+        minutes / 60,
+        minutes % 60
+    )
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&HistoryEntry> for JsonValue {
+    fn from(value: &HistoryEntry) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        if let Some(year) = value.year {
+            map.insert("year".into(), JsonValue::String(year.to_string()));
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+        map.insert(
+            "commit".into(),
+            match &value.commit {
+                Some(x) => JsonValue::String(x.clone()),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert("profile".into(), JsonValue::String(value.profile.clone()));
+        map.insert("machine".into(), JsonValue::String(value.machine.clone()));
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.timings.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for HistoryEntry {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected history entry to be a JSON object.")?;
+
+        let year = match json.get("year") {
+            None => None,
+            Some(v) => Some(
+                v.get::<String>()
+                    .and_then(|year| Year::from_str(year).ok())
+                    .ok_or("Expected entry.year to be a Year struct.")?,
+            ),
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u64)
+            .ok_or("Expected entry.timestamp to be a number.")?;
+
+        let commit = json
+            .get("commit")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected entry.commit to be null or string.")?;
+
+        let profile = json
+            .get("profile")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected entry.profile to be a string.")?;
+
+        let machine = json
+            .get("machine")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected entry.machine to be a string.")?;
+
+        let data = json
+            .get("data")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .ok_or("Expected entry.data to be an array.")?;
+
+        Ok(HistoryEntry {
+            year,
+            timestamp,
+            commit: commit.cloned(),
+            profile: profile.clone(),
+            machine: machine.clone(),
+            timings: Timings {
+                data: data
+                    .iter()
+                    .map(Timing::try_from)
+                    .collect::<Result<_, _>>()?,
+            },
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_parts, format_timestamp, parse, HistoryEntry};
+    use crate::{
+        day,
+        template::timings::{Timing, Timings},
+        year,
+    };
+    use tinyjson::JsonValue;
+
+    #[test]
+    fn roundtrips_entries() {
+        let entry = HistoryEntry {
+            year: None,
+            timestamp: 1_733_011_200,
+            commit: Some("3bc0008".into()),
+            profile: "release".into(),
+            machine: "laptop".into(),
+            timings: Timings {
+                data: vec![Timing {
+                    part_1: Some("451.1µs".into()),
+                    total_nanos: 451_100.0,
+                    ..Timing::new(None, day!(9))
+                }],
+            },
+        };
+
+        let line = JsonValue::from(&entry).stringify().unwrap();
+        let parsed = parse(&format!("{line}\n{line}\n")).unwrap();
+
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed[0].timestamp, entry.timestamp);
+        assert_eq!(parsed[0].commit, entry.commit);
+        assert_eq!(parsed[0].machine, "laptop");
+        assert_eq!(
+            parsed[0].timing(None, day!(9)).unwrap().part_1.as_deref(),
+            Some("451.1µs")
+        );
+        assert!(parsed[0].timing(None, day!(1)).is_none());
+    }
+
+    #[test]
+    fn keeps_years_of_entries() {
+        let timings = Timings {
+            data: vec![Timing::new(None, day!(9))],
+        };
+        let entry = HistoryEntry::new(Some(year!(2023)), timings, true);
+        assert_eq!(entry.timings.data[0].year, Some(year!(2023)));
+
+        let line = JsonValue::from(&entry).stringify().unwrap();
+        let parsed = parse(&line).unwrap();
+        assert_eq!(parsed[0].year, Some(year!(2023)));
+        assert!(parsed[0].timing(Some(year!(2023)), day!(9)).is_some());
+    }
+
+    #[test]
+    fn formats_parse_times() {
+        let timing = Timing {
+            part_1: Some("451.1µs".into()),
+            parse: Some("1.2ms".into()),
+            ..Timing::new(None, day!(9))
+        };
+
+        assert_eq!(
+            format_parts(&timing, false),
+            "Part 1: 451.1µs    Part 2: -         "
+        );
+        assert_eq!(
+            format_parts(&timing, true),
+            "Parse: 1.2ms      Part 1: 451.1µs    Part 2: -         "
+        );
+    }
+
+    #[test]
+    fn formats_timestamps() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00");
+        assert_eq!(format_timestamp(1_733_011_200), "2024-12-01 00:00");
+        assert_eq!(format_timestamp(1_709_210_096), "2024-02-29 12:34");
+    }
+}
@@ -0,0 +1,148 @@
+/// Selects the input a solution binary runs against.
+///
+/// By default, solutions read their puzzle input. `cargo solve` forwards `--input <path>` (`-` for stdin)
+/// and `--example [<n>]` to the solution binary to run it against other inputs.
+use std::{
+    env,
+    io::{self, Read},
+    path::PathBuf,
+    process,
+};
+
+use crate::template::{Day, Workspace};
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum Source {
+    /// The puzzle input, e.g. `data/inputs/01.txt`.
+    #[default]
+    Puzzle,
+    File(PathBuf),
+    Stdin,
+    /// An example, e.g. `data/examples/01.txt` or `data/examples/01-2.txt` for `Some(2)`.
+    Example(Option<u8>),
+}
+
+impl Source {
+    /// Parse the arguments of a solution binary. Other arguments, e.g. `--time`, are ignored.
+    pub fn parse(args: &[String]) -> Result<Self, String> {
+        let position = |flag: &str| args.iter().position(|arg| arg == flag);
+
+        match (position("--input"), position("--example")) {
+            (Some(_), Some(_)) => Err("`--input` and `--example` cannot be combined.".into()),
+            (Some(i), None) => match args.get(i + 1).map(String::as_str) {
+                Some("-") => Ok(Source::Stdin),
+                Some(path) if !path.starts_with("--") => Ok(Source::File(path.into())),
+                _ => Err("expected a path or `-` after `--input`.".into()),
+            },
+            (None, Some(i)) => match args.get(i + 1).and_then(|n| n.parse().ok()) {
+                Some(n) => Ok(Source::Example(Some(n))),
+                None => Ok(Source::Example(None)),
+            },
+            (None, None) => Ok(Source::Puzzle),
+        }
+    }
+
+    /// The source selected by the arguments of the current process.
+    pub fn from_args() -> Self {
+        let args: Vec<String> = env::args().collect();
+        Self::parse(&args).unwrap_or_else(|e| {
+            eprintln!("{e}");
+            process::exit(1);
+        })
+    }
+
+    /// Arguments that pass this source on to a solution binary.
+    pub fn args(&self) -> Vec<String> {
+        match self {
+            Source::Puzzle => vec![],
+            Source::File(path) => vec!["--input".into(), path.display().to_string()],
+            Source::Stdin => vec!["--input".into(), "-".into()],
+            Source::Example(None) => vec!["--example".into()],
+            Source::Example(Some(n)) => vec!["--example".into(), n.to_string()],
+        }
+    }
+
+    /// Whether this is the puzzle input, which answers are recorded and submitted for.
+    pub fn is_puzzle(&self) -> bool {
+        *self == Source::Puzzle
+    }
+
+    /// Read the input of `day` from this source.
+    pub fn read(&self, workspace: Workspace, day: Day) -> String {
+        match self {
+            Source::Puzzle => workspace.read_file("inputs", day),
+            Source::Example(None) => workspace.read_file("examples", day),
+            Source::Example(Some(n)) => workspace.read_file_part("examples", day, *n),
+            Source::File(path) => std::fs::read_to_string(path).unwrap_or_else(|e| {
+                eprintln!("could not open input file {}: {e}", path.display());
+                process::exit(1);
+            }),
+            Source::Stdin => {
+                let mut input = String::new();
+                if let Err(e) = io::stdin().read_to_string(&mut input) {
+                    eprintln!("could not read input from stdin: {e}");
+                    process::exit(1);
+                }
+                input
+            }
+        }
+    }
+}
+
+/// Read the input selected by the arguments of the current process.
+pub fn read(workspace: Workspace, day: Day) -> String {
+    Source::from_args().read(workspace, day)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Source;
+
+    fn parse(args: &[&str]) -> Result<Source, String> {
+        let args: Vec<String> = args.iter().map(ToString::to_string).collect();
+        Source::parse(&args)
+    }
+
+    #[test]
+    fn parses_input_sources() {
+        assert_eq!(parse(&["01", "--time"]), Ok(Source::Puzzle));
+        assert_eq!(
+            parse(&["01", "--input", "other.txt", "--time"]),
+            Ok(Source::File("other.txt".into()))
+        );
+        assert_eq!(parse(&["01", "--input", "-"]), Ok(Source::Stdin));
+        assert_eq!(parse(&["01", "--example"]), Ok(Source::Example(None)));
+        assert_eq!(
+            parse(&["01", "--example", "2", "--time"]),
+            Ok(Source::Example(Some(2)))
+        );
+        assert_eq!(
+            parse(&["01", "--example", "--time"]),
+            Ok(Source::Example(None))
+        );
+    }
+
+    #[test]
+    fn roundtrips_args() {
+        for source in [
+            Source::Puzzle,
+            Source::File("other.txt".into()),
+            Source::Stdin,
+            Source::Example(None),
+            Source::Example(Some(3)),
+        ] {
+            let mut args = vec!["01".to_string()];
+            args.extend(source.args());
+            assert_eq!(Source::parse(&args), Ok(source));
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_input_sources() {
+        assert!(parse(&["01", "--input"]).is_err());
+        assert!(parse(&["01", "--input", "--time"]).is_err());
+        assert!(parse(&["01", "--input", "a.txt", "--example"]).is_err());
+    }
+}
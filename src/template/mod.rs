@@ -1,44 +1,71 @@
-use std::{env, fs};
-
-pub mod aoc_cli;
+pub mod answers;
+pub mod aoc_client;
+pub mod bench_config;
+pub mod calendar;
 pub mod commands;
+pub mod input;
+pub mod registry;
 pub mod runner;
+pub mod timeout;
 
 pub use day::*;
+pub use workspace::*;
+pub use year::*;
 
+mod date;
 mod day;
-mod readme_benchmarks;
-mod run_multi;
+mod heap;
+mod history;
+mod puzzle;
+mod puzzle_reader;
+pub mod readme_benchmarks;
+pub mod readme_stars;
+pub mod report;
+pub mod results;
+pub mod run_multi;
+mod stats;
+mod submissions;
+mod table_settings;
+mod templates;
 mod timings;
+mod workspace;
+mod year;
+
+// NOTE: declared in the library so that it is shared by every binary, including `registry`.
+#[cfg(feature = "dhat-heap")]
+#[global_allocator]
+static ALLOC: dhat::Alloc = dhat::Alloc;
+
+#[cfg(all(feature = "heap-stats", not(feature = "dhat-heap")))]
+#[global_allocator]
+static ALLOC: heap::CountingAlloc = heap::CountingAlloc;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
 /// Helper function that reads a text file to a string.
+/// Reads from the flat layout, solutions namespaced by year use [`Workspace::read_file`].
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    Workspace::flat(None).read_file(folder, day)
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
+/// Reads from the flat layout, solutions namespaced by year use [`Workspace::read_file_part`].
 #[must_use]
 pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{day}-{part}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    Workspace::flat(None).read_file_part(folder, day, part)
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Creates the constants `DAY` and `WORKSPACE` and sets up the input and runner for each part.
+/// The workspace is derived from the name of the file, e.g. `src/bin/2024-01.rs` is namespaced by year.
+/// The input defaults to the puzzle input and can be changed with `--input` or `--example`, see [`input`].
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// With `parse`, e.g. `solution!(1, parse)`, the input is parsed once by a `fn parse(input: &str) -> T`
+/// and both parts receive a `&T`. Parsing is timed separately from the parts.
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
@@ -50,19 +77,51 @@ macro_rules! solution {
     ($day:expr, 2) => {
         $crate::solution!(@impl $day, [part_two, 2]);
     };
+    ($day:expr, parse) => {
+        $crate::solution!(@parse $day, [part_one, 1] [part_two, 2]);
+    };
+    ($day:expr, parse, 1) => {
+        $crate::solution!(@parse $day, [part_one, 1]);
+    };
+    ($day:expr, parse, 2) => {
+        $crate::solution!(@parse $day, [part_two, 2]);
+    };
 
     (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@setup $day, |input| {
+            use $crate::template::runner::*;
+            $( run_part($func, input, WORKSPACE, DAY, $part); )*
+        });
+    };
+
+    (@parse $day:expr, $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@setup $day, |input| {
+            use $crate::template::runner::*;
+            let input = run_parse(parse, input, DAY);
+            $( run_part($func, &input, WORKSPACE, DAY, $part); )*
+        });
+    };
+
+    (@setup $day:expr, $run:expr) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
-        #[cfg(feature = "dhat-heap")]
-        #[global_allocator]
-        static ALLOC: dhat::Alloc = dhat::Alloc;
+        /// Where the input and data of the current day live.
+        const WORKSPACE: $crate::template::Workspace =
+            $crate::template::Workspace::__from_source_path(file!());
+
+        /// Registers the solution for in-process runs.
+        pub const SOLUTION: $crate::template::registry::Solution =
+            $crate::template::registry::Solution {
+                day: DAY,
+                workspace: WORKSPACE,
+                run: $run,
+            };
 
         fn main() {
-            use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            let input = $crate::template::input::read(WORKSPACE, DAY);
+            (SOLUTION.run)(&input);
+            $crate::template::runner::exit_on_regression();
         }
     };
 }
@@ -0,0 +1,153 @@
+/// An example of a part of the puzzle, read from its description in `data/puzzles` as written by `download`.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Example {
+    /// The example input of the part, `None` if the part reuses the example of part one.
+    pub input: Option<String>,
+    /// The answer for the example.
+    pub answer: Option<String>,
+}
+
+/// Split a puzzle description into the description of each part.
+pub fn parts(markdown: &str) -> Vec<&str> {
+    match markdown.find("## --- Part Two ---") {
+        Some(i) => vec![&markdown[..i], &markdown[i..]],
+        None => vec![markdown],
+    }
+}
+
+/// The examples of each part that the description contains.
+/// The example of a part is the first code block that follows a mention of an example, its answer
+/// is the last emphasized code in the description of that part, e.g. `` `*143*` ``.
+pub fn examples(markdown: &str) -> Vec<Example> {
+    parts(markdown)
+        .into_iter()
+        .map(|part| Example {
+            input: example_input(part),
+            answer: example_answer(part),
+        })
+        .collect()
+}
+
+/// Code blocks of a part, along with the text preceding each block.
+fn code_blocks(part: &str) -> Vec<(&str, String)> {
+    let mut blocks = vec![];
+    let mut rest = part;
+
+    while let Some(start) = rest.find("```\n") {
+        let preceding = &rest[..start];
+        let content_start = start + 4;
+        let Some(len) = rest[content_start..].find("```") else {
+            break;
+        };
+
+        blocks.push((
+            preceding,
+            rest[content_start..content_start + len].to_string(),
+        ));
+        rest = &rest[content_start + len + 3..];
+    }
+
+    blocks
+}
+
+fn example_input(part: &str) -> Option<String> {
+    let blocks = code_blocks(part);
+
+    blocks
+        .iter()
+        .find(|(preceding, _)| preceding.to_lowercase().contains("example"))
+        .or(blocks.first())
+        .map(|(_, block)| block.clone())
+}
+
+fn example_answer(part: &str) -> Option<String> {
+    // text outside of code blocks, in which odd segments are inline code.
+    let prose: String = part.split("```").step_by(2).collect::<Vec<_>>().join("\n");
+    let segments: Vec<&str> = prose.split('`').collect();
+
+    // both `<code><em>` and `<em><code>` are used to highlight answers.
+    (1..segments.len())
+        .step_by(2)
+        .filter_map(|i| {
+            let code = segments[i];
+            if let Some(answer) = code.strip_prefix('*').and_then(|c| c.strip_suffix('*')) {
+                return Some(answer);
+            }
+            let surrounded = segments[i - 1].ends_with('*')
+                && segments.get(i + 1).is_some_and(|s| s.starts_with('*'));
+            surrounded.then_some(code)
+        })
+        .rfind(|answer| !answer.is_empty())
+        .map(str::to_string)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{examples, Example};
+
+    #[test]
+    fn extracts_examples_and_answers() {
+        let markdown = [
+            "## --- Day 1: Historian Hysteria ---",
+            "",
+            "Throughout the Chief's office, the historically significant locations are listed.",
+            "",
+            "```",
+            "not the example",
+            "```",
+            "",
+            "For example:",
+            "",
+            "```",
+            "3   4",
+            "4   3",
+            "```",
+            "",
+            "In the example above, this is `2`, so the total distance is `*11*`.",
+            "",
+            "## --- Part Two ---",
+            "",
+            "Here is the same example list again. The similarity score is *`31`*.",
+            "",
+        ]
+        .join("\n");
+
+        assert_eq!(
+            examples(&markdown),
+            vec![
+                Example {
+                    input: Some("3   4\n4   3\n".into()),
+                    answer: Some("11".into()),
+                },
+                Example {
+                    input: None,
+                    answer: Some("31".into()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn extracts_example_of_part_two() {
+        let markdown = [
+            "For example:",
+            "```",
+            "xmul(2,4)",
+            "```",
+            "Adding up the results produces `*161*`.",
+            "## --- Part Two ---",
+            "This time, the example is different:",
+            "```",
+            "don't()mul(5,5)",
+            "```",
+            "This time, the sum of the results is `*48*`.",
+        ]
+        .join("\n");
+
+        let examples = examples(&markdown);
+        assert_eq!(examples[1].input.as_deref(), Some("don't()mul(5,5)\n"));
+        assert_eq!(examples[1].answer.as_deref(), Some("48"));
+    }
+}
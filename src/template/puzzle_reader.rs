@@ -0,0 +1,295 @@
+/// Renders puzzle descriptions in `data/puzzles` for reading them in the terminal.
+use crate::template::{ANSI_BOLD, ANSI_RESET};
+
+const ANSI_GREEN: &str = "\x1b[32m";
+const ANSI_YELLOW: &str = "\x1b[33m";
+const ANSI_CYAN: &str = "\x1b[36m";
+const ANSI_DIM: &str = "\x1b[2m";
+const ANSI_UNDERLINE: &str = "\x1b[4m";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Style {
+    /// Highlights titles, code and emphasized text such as answers with escape codes.
+    Color,
+    /// Keeps emphasized text in `*` and does not use escape codes, so that the output can be piped to a pager.
+    Plain,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Inline {
+    Text,
+    Code,
+    Emphasis,
+    Link,
+}
+
+/// A word of a paragraph, along with the length it takes up in the terminal.
+struct Word {
+    rendered: String,
+    width: usize,
+}
+
+/// Render a puzzle description, wrapping paragraphs and lists at `width` columns.
+/// Code blocks are kept as they are.
+pub fn render(markdown: &str, style: Style, width: usize) -> String {
+    let mut out = vec![];
+    let mut lines = markdown.lines().peekable();
+
+    while let Some(line) = lines.next() {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        if let Some(heading) = line.strip_prefix("## ") {
+            let title = heading.trim_start_matches("--- ").trim_end_matches(" ---");
+            // part two follows part one, separate them.
+            if !out.is_empty() {
+                out.push(paint(&"─".repeat(width), ANSI_DIM, style));
+                out.push(String::new());
+            }
+            out.push(paint(title, &format!("{ANSI_BOLD}{ANSI_GREEN}"), style));
+        } else if line.starts_with("```") {
+            for code in lines.by_ref().take_while(|line| !line.starts_with("```")) {
+                out.push(format!("    {}", paint(code, ANSI_CYAN, style)));
+            }
+        } else if let Some(item) = line.strip_prefix("- ") {
+            out.extend(wrap(&words(item, style), width, "  - ", "    "));
+            // consecutive list items are not separated by blank lines.
+            if lines.peek().is_some_and(|next| next.starts_with("- ")) {
+                continue;
+            }
+        } else {
+            let mut paragraph = line.to_string();
+            while let Some(next) = lines.next_if(|next| is_paragraph(next)) {
+                paragraph.push(' ');
+                paragraph.push_str(next);
+            }
+            out.extend(wrap(&words(&paragraph, style), width, "", ""));
+        }
+
+        out.push(String::new());
+    }
+
+    out.join("\n")
+}
+
+fn is_paragraph(line: &str) -> bool {
+    !line.trim().is_empty()
+        && !line.starts_with("## ")
+        && !line.starts_with("```")
+        && !line.starts_with("- ")
+}
+
+fn paint(text: &str, code: &str, style: Style) -> String {
+    match style {
+        Style::Color => format!("{code}{text}{ANSI_RESET}"),
+        Style::Plain => text.to_string(),
+    }
+}
+
+/// Split inline markdown into runs of text, code, emphasis and links.
+fn inline_runs(text: &str) -> Vec<(Inline, String)> {
+    let mut runs: Vec<(Inline, String)> = vec![];
+    let mut emphasized = false;
+    let mut rest = text;
+
+    let push = |runs: &mut Vec<(Inline, String)>, kind: Inline, s: &str| match runs.last_mut() {
+        Some((last, text)) if *last == kind => text.push_str(s),
+        _ => runs.push((kind, s.to_string())),
+    };
+
+    while let Some(c) = rest.chars().next() {
+        let kind = if emphasized {
+            Inline::Emphasis
+        } else {
+            Inline::Text
+        };
+
+        match c {
+            '`' => {
+                let Some(len) = rest[1..].find('`') else {
+                    push(&mut runs, kind, &rest[1..]);
+                    break;
+                };
+                let code = &rest[1..1 + len];
+                // answers are emphasized inside of code, e.g. `*11*`. Other asterisks in code are literal.
+                match code.strip_prefix('*').and_then(|c| c.strip_suffix('*')) {
+                    Some(answer) if !answer.is_empty() => push(&mut runs, Inline::Emphasis, answer),
+                    _ if emphasized => push(&mut runs, Inline::Emphasis, code),
+                    _ => push(&mut runs, Inline::Code, code),
+                }
+                rest = &rest[len + 2..];
+                continue;
+            }
+            '*' => emphasized = !emphasized,
+            '[' => {
+                let link = rest[1..]
+                    .split_once("](")
+                    .filter(|(label, _)| !label.contains(']'))
+                    .and_then(|(label, target)| {
+                        target.find(')').map(|end| (label, label.len() + end + 3))
+                    });
+                if let Some((label, len)) = link {
+                    push(&mut runs, Inline::Link, label);
+                    rest = &rest[1 + len..];
+                    continue;
+                }
+                push(&mut runs, kind, "[");
+            }
+            c => push(&mut runs, kind, c.encode_utf8(&mut [0; 4])),
+        }
+
+        rest = &rest[c.len_utf8()..];
+    }
+
+    runs
+}
+
+/// Split a paragraph into words, styling each run.
+fn words(text: &str, style: Style) -> Vec<Word> {
+    let mut words: Vec<Word> = vec![];
+    let mut at_boundary = true;
+
+    for (kind, run) in inline_runs(text) {
+        let parts: Vec<&str> = run.split(' ').collect();
+        let last = parts.iter().rposition(|part| !part.is_empty());
+
+        for (i, part) in parts.iter().enumerate() {
+            if i > 0 {
+                at_boundary = true;
+            }
+            if part.is_empty() {
+                continue;
+            }
+
+            let rendered = match (style, kind) {
+                (Style::Plain, Inline::Emphasis) => {
+                    // mark the start and end of the emphasized run, not of each word.
+                    let open = if i == 0 || parts[..i].iter().all(|p| p.is_empty()) {
+                        "*"
+                    } else {
+                        ""
+                    };
+                    let close = if Some(i) == last { "*" } else { "" };
+                    format!("{open}{part}{close}")
+                }
+                (Style::Plain, _) | (_, Inline::Text) => part.to_string(),
+                (Style::Color, Inline::Code) => paint(part, ANSI_CYAN, style),
+                (Style::Color, Inline::Emphasis) => {
+                    paint(part, &format!("{ANSI_BOLD}{ANSI_YELLOW}"), style)
+                }
+                (Style::Color, Inline::Link) => paint(part, ANSI_UNDERLINE, style),
+            };
+            let width = match style {
+                Style::Plain => rendered.chars().count(),
+                Style::Color => part.chars().count(),
+            };
+
+            match words.last_mut() {
+                // runs that are not separated by a space, e.g. `*11*.`, belong to the same word.
+                Some(word) if !at_boundary => {
+                    word.rendered.push_str(&rendered);
+                    word.width += width;
+                }
+                _ => words.push(Word { rendered, width }),
+            }
+            at_boundary = false;
+        }
+
+        if run.ends_with(' ') {
+            at_boundary = true;
+        }
+    }
+
+    words
+}
+
+/// Wrap words into lines of at most `width` columns. Words longer than a line are not split.
+fn wrap(words: &[Word], width: usize, first_indent: &str, indent: &str) -> Vec<String> {
+    let mut lines = vec![];
+    let mut line = first_indent.to_string();
+    let mut line_width = first_indent.chars().count();
+    let mut is_empty = true;
+
+    for word in words {
+        if !is_empty && line_width + 1 + word.width > width {
+            lines.push(line);
+            line = indent.to_string();
+            line_width = indent.chars().count();
+            is_empty = true;
+        }
+        if !is_empty {
+            line.push(' ');
+            line_width += 1;
+        }
+        line.push_str(&word.rendered);
+        line_width += word.width;
+        is_empty = false;
+    }
+
+    if !is_empty {
+        lines.push(line);
+    }
+    lines
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{render, Style};
+
+    const PUZZLE: &str = "## --- Day 1: Historian Hysteria ---
+
+The *Chief Historian* is always present for the big Christmas sleigh launch, but nobody has seen him in months.
+
+For example:
+
+```
+3   4
+4   3
+```
+
+- The `1` in the [left list](/2024/about).
+- The total distance is `*11*`.
+
+## --- Part Two ---
+
+Your puzzle answer was `*31*`.
+";
+
+    #[test]
+    fn renders_plain_descriptions() {
+        let expected = [
+            "Day 1: Historian Hysteria",
+            "",
+            "The *Chief Historian* is always present for the big",
+            "Christmas sleigh launch, but nobody has seen him in months.",
+            "",
+            "For example:",
+            "",
+            "    3   4",
+            "    4   3",
+            "",
+            "  - The 1 in the left list.",
+            "  - The total distance is *11*.",
+            "",
+            "────────────────────────────────────────────────────────────",
+            "",
+            "Part Two",
+            "",
+            "Your puzzle answer was *31*.",
+            "",
+        ]
+        .join("\n");
+
+        assert_eq!(render(PUZZLE, Style::Plain, 60), expected);
+    }
+
+    #[test]
+    fn highlights_answers() {
+        let rendered = render(PUZZLE, Style::Color, 80);
+        assert!(rendered.contains("is \x1b[1m\x1b[33m11\x1b[0m."));
+        assert!(rendered.contains("    \x1b[36m3   4\x1b[0m"));
+    }
+}
@@ -1,9 +1,13 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+use std::{collections::HashMap, fs, io};
 
-use crate::template::timings::Timings;
-use crate::template::Day;
+use crate::template::calendar::Calendar;
+use crate::template::heap::format_bytes;
+use crate::template::stats::{format_nanos, Stats};
+use crate::template::table_settings::{Column, Sort, TableSettings};
+use crate::template::timings::{Timing, Timings};
+use crate::template::{Workspace, Year};
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
@@ -20,18 +24,49 @@ impl From<std::io::Error> for Error {
     }
 }
 
-pub struct TablePosition {
-    pos_start: usize,
-    pos_end: usize,
+/// The value shown for each part in the benchmark table.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Metric {
+    /// The arithmetic mean of all samples.
+    #[default]
+    Mean,
+    /// The median of all samples and their standard deviation, e.g. `72.1µs ± 3.2µs`.
+    Median,
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+/// The workspace and calendar of each year in the table, see [`locate_years`].
+/// Years that are missing use the layout namespaced by year and the default calendar.
+pub(crate) type Layouts = HashMap<Option<Year>, (Workspace, Calendar)>;
+
+/// Locate the workspace and read the calendar of every year of `timings`.
+pub(crate) fn locate_years(timings: &Timings) -> Layouts {
+    timings
+        .data
+        .iter()
+        .map(|t| {
+            let calendar = Calendar::from_env(t.year).unwrap_or_default();
+            (t.year, (Workspace::locate(t.year), calendar))
+        })
+        .collect()
+}
+
+pub struct TablePosition {
+    pub(crate) pos_start: usize,
+    pub(crate) pos_end: usize,
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+/// Locate the table enclosed by a pair of `marker`s. A single marker is replaced with the table.
+/// Markers are only recognized on lines of their own, so that prose can mention them.
+pub(crate) fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let mut offset = 0;
+    let mut matches = vec![];
+
+    for line in readme.split_inclusive('\n') {
+        if !line.trim().is_empty() && line.replace(marker, "").trim().is_empty() {
+            matches.extend(line.match_indices(marker).map(|(i, m)| (offset + i, m)));
+        }
+        offset += line.len();
+    }
 
     if matches.len() > 2 {
         return Err(Error::Parser(
@@ -52,76 +87,278 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
+fn format_cell(part: Option<String>, stats: Option<Stats>, metric: Metric) -> String {
+    match (metric, stats) {
+        (Metric::Median, Some(stats)) => format!(
+            "{} ± {}",
+            format_nanos(stats.median),
+            format_nanos(stats.stddev)
+        ),
+        _ => part.unwrap_or_else(|| "-".into()),
+    }
+}
+
+/// The larger peak heap usage of both parts of a day.
+fn format_peak_heap(timing: &Timing) -> String {
+    [timing.part_1_heap, timing.part_2_heap]
+        .iter()
+        .flatten()
+        .map(|heap| heap.peak_bytes)
+        .max()
+        .map_or_else(|| "-".into(), format_bytes)
+}
+
+/// The number of allocations of both parts of a day.
+fn format_allocations(timing: &Timing) -> String {
+    let heaps = [timing.part_1_heap, timing.part_2_heap];
+    if heaps.iter().all(Option::is_none) {
+        return "-".into();
+    }
+    heaps
+        .iter()
+        .flatten()
+        .map(|heap| heap.allocations)
+        .sum::<u64>()
+        .to_string()
+}
+
+fn format_samples(timing: &Timing) -> String {
+    let format = |samples: Option<u64>| samples.map_or_else(|| "-".into(), |s| s.to_string());
+    format!(
+        "{} / {}",
+        format(timing.part_1_samples),
+        format(timing.part_2_samples)
+    )
+}
+
+/// A bar of ten characters, filled by `ratio`, e.g. `████▌     `.
+fn format_sparkline(ratio: f64) -> String {
+    const WIDTH: usize = 10;
+    const PARTIAL: [char; 8] = [' ', '▏', '▎', '▍', '▌', '▋', '▊', '▉'];
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let eighths = (ratio.clamp(0.0, 1.0) * (WIDTH * 8) as f64).round() as usize;
+
+    let (full, partial) = (eighths / 8, eighths % 8);
+
+    let mut bar = "█".repeat(full);
+    if partial > 0 {
+        bar.push(PARTIAL[partial]);
+    }
+
+    let len = bar.chars().count();
+    bar + &" ".repeat(WIDTH - len)
+}
+
+/// Render the benchmark table as Markdown, headed by a `{prefix} Benchmarks` heading.
+pub(crate) fn markdown_table(
+    prefix: &str,
+    timings: &Timings,
+    layouts: &Layouts,
+    total_millis: f64,
+    metric: Metric,
+    settings: &TableSettings,
+) -> Vec<String> {
     let header = format!("{prefix} Benchmarks");
 
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
-
-    for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
-        lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
-            timing.day.into_inner(),
-            path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
-        ));
+    // only show heap columns if at least one day was recorded with the `heap-stats` feature.
+    let has_heap = timings
+        .data
+        .iter()
+        .any(|t| t.part_1_heap.is_some() || t.part_2_heap.is_some());
+
+    // only show the parse column if at least one day parses its input once.
+    let has_parse = timings.data.iter().any(|t| t.parse.is_some());
+
+    let columns: Vec<Column> = settings
+        .columns
+        .iter()
+        .copied()
+        .filter(|c| has_heap || !matches!(c, Column::Heap | Column::Allocations))
+        .filter(|c| has_parse || *c != Column::Parse)
+        .collect();
+
+    let slowest = timings
+        .data
+        .iter()
+        .map(|t| t.total_nanos)
+        .fold(0.0, f64::max);
+
+    let mut lines: Vec<String> = vec![header, String::new()];
+
+    // timings are listed in one table per year.
+    let mut years: Vec<Option<Year>> = timings.data.iter().map(|t| t.year).collect();
+    years.sort_unstable();
+    years.dedup();
+
+    for year in years {
+        let (workspace, calendar) = layouts.get(&year).copied().unwrap_or_else(|| {
+            let workspace = year.map_or(Workspace::flat(None), Workspace::namespaced);
+            (workspace, Calendar::default())
+        });
+
+        if let Some(year) = year {
+            lines.push(format!("### {year}"));
+            lines.push(String::new());
+        }
+
+        let titles: Vec<&str> = columns.iter().map(|c| c.title()).collect();
+        lines.push(format!("| Day | {} |", titles.join(" | ")));
+        lines.push(format!("| :---: |{}", " :---: |".repeat(columns.len())));
+
+        // skip stale timings of days that are not part of the event.
+        let mut rows: Vec<&Timing> = timings
+            .data
+            .iter()
+            .filter(|t| t.year == year && calendar.contains(t.day))
+            .collect();
+
+        if settings.sort == Sort::Cost {
+            rows.sort_by(|a, b| b.total_nanos.total_cmp(&a.total_nanos));
+        }
+
+        for timing in rows {
+            let cells: Vec<String> = columns
+                .iter()
+                .map(|column| match column {
+                    Column::Parse => format!(
+                        "`{}`",
+                        format_cell(timing.parse.clone(), timing.parse_stats, metric)
+                    ),
+                    Column::Part1 => format!(
+                        "`{}`",
+                        format_cell(timing.part_1.clone(), timing.part_1_stats, metric)
+                    ),
+                    Column::Part2 => format!(
+                        "`{}`",
+                        format_cell(timing.part_2.clone(), timing.part_2_stats, metric)
+                    ),
+                    Column::Total => format!("`{}`", format_nanos(timing.total_nanos)),
+                    Column::Share if total_millis > 0.0 => format!(
+                        "{:.1}%",
+                        timing.total_nanos / (total_millis * 1_000_000.0) * 100.0
+                    ),
+                    Column::Share => "-".into(),
+                    Column::Samples => format!("`{}`", format_samples(timing)),
+                    Column::Heap => format!("`{}`", format_peak_heap(timing)),
+                    Column::Allocations => format!("`{}`", format_allocations(timing)),
+                    Column::Puzzle => format!(
+                        "[Puzzle](./{})",
+                        workspace
+                            .data_path("puzzles", &format!("{}.md", timing.day))
+                            .display()
+                    ),
+                    Column::Sparkline if slowest > 0.0 => {
+                        format!("`{}`", format_sparkline(timing.total_nanos / slowest))
+                    }
+                    Column::Sparkline => format!("`{}`", format_sparkline(0.0)),
+                })
+                .collect();
+
+            lines.push(format!(
+                "| [Day {}]({}) | {} |",
+                timing.day.into_inner(),
+                workspace.bin_path(timing.day),
+                cells.join(" | ")
+            ));
+        }
+
+        lines.push(String::new());
     }
 
-    lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
+    lines
+}
+
+fn construct_table(
+    prefix: &str,
+    timings: &Timings,
+    layouts: &Layouts,
+    total_millis: f64,
+    metric: Metric,
+    settings: &TableSettings,
+) -> String {
+    let mut lines = vec![MARKER.into()];
+    lines.extend(markdown_table(
+        prefix,
+        timings,
+        layouts,
+        total_millis,
+        metric,
+        settings,
+    ));
     lines.push(MARKER.into());
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+fn update_content(
+    s: &mut String,
+    timings: Timings,
+    layouts: &Layouts,
+    total_millis: f64,
+    metric: Metric,
+    settings: &TableSettings,
+) -> Result<(), Error> {
+    let positions = locate_table(s, MARKER)?;
+    let table = construct_table("##", &timings, layouts, total_millis, metric, settings);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
-pub fn update(timings: Timings) -> Result<(), Error> {
+pub fn update(timings: Timings, metric: Metric) -> Result<(), Error> {
     let path = "README.md";
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
     let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
+    let layouts = locate_years(&timings);
+    update_content(
+        &mut readme,
+        timings,
+        &layouts,
+        total_millis,
+        metric,
+        &TableSettings::read_from_file(),
+    )?;
     fs::write(path, &readme)?;
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use super::{update_content, Layouts, Metric, MARKER};
+    use crate::template::table_settings::{Column, Sort, TableSettings};
+    use crate::{
+        day,
+        template::{
+            calendar::Calendar,
+            heap::HeapStats,
+            stats::Stats,
+            timings::{Timing, Timings},
+            Workspace,
+        },
+        year,
+    };
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
-                    day: day!(1),
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
+                    ..Timing::new(None, day!(1))
                 },
                 Timing {
-                    day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
+                    ..Timing::new(None, day!(2))
                 },
                 Timing {
-                    day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: Some("50ms".into()),
                     total_nanos: 9e+10,
+                    ..Timing::new(None, day!(4))
                 },
             ],
         }
@@ -131,28 +368,68 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            get_mock_timings(),
+            &Layouts::new(),
+            190.0,
+            Metric::Mean,
+            &TableSettings::default(),
+        )
+        .unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            get_mock_timings(),
+            &Layouts::new(),
+            190.0,
+            Metric::Mean,
+            &TableSettings::default(),
+        )
+        .unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            get_mock_timings(),
+            &Layouts::new(),
+            190.0,
+            Metric::Mean,
+            &TableSettings::default(),
+        )
+        .unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            get_mock_timings(),
+            &Layouts::new(),
+            190.0,
+            Metric::Mean,
+            &TableSettings::default(),
+        )
+        .unwrap();
+        update_content(
+            &mut s,
+            get_mock_timings(),
+            &Layouts::new(),
+            190.0,
+            Metric::Mean,
+            &TableSettings::default(),
+        )
+        .unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -160,7 +437,15 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            get_mock_timings(),
+            &Layouts::new(),
+            190.0,
+            Metric::Mean,
+            &TableSettings::default(),
+        )
+        .unwrap();
         let expected = [
             "foo",
             "bar",
@@ -168,7 +453,7 @@ mod tests {
             "## Benchmarks",
             "",
             "| Day | Part 1 | Part 2 |",
-            "| :---: | :---: | :---:  |",
+            "| :---: | :---: | :---: |",
             "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
             "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
             "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
@@ -180,4 +465,172 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_median_benchmarks() {
+        let mut timings = get_mock_timings();
+        timings.data[0].part_1_stats = Some(Stats {
+            mean: 10_000_000.0,
+            median: 9_000_000.0,
+            min: 8_000_000.0,
+            max: 30_000_000.0,
+            p95: 20_000_000.0,
+            stddev: 1_500_000.0,
+        });
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(
+            &mut s,
+            timings,
+            &Layouts::new(),
+            190.0,
+            Metric::Median,
+            &TableSettings::default(),
+        )
+        .unwrap();
+        assert!(s.contains("| [Day 1](./src/bin/01.rs) | `9.0ms ± 1.5ms` | `20ms` |"));
+        assert!(s.contains("| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |"));
+    }
+
+    #[test]
+    fn format_heap_benchmarks() {
+        let mut timings = get_mock_timings();
+        timings.data[0].part_2_heap = Some(HeapStats {
+            total_bytes: 8192,
+            peak_bytes: 2048,
+            allocations: 4,
+        });
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(
+            &mut s,
+            timings,
+            &Layouts::new(),
+            190.0,
+            Metric::Mean,
+            &TableSettings::default(),
+        )
+        .unwrap();
+        assert!(s.contains("| Day | Part 1 | Part 2 | Peak heap |"));
+        assert!(s.contains("| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` | `2.0 KiB` |"));
+        assert!(s.contains("| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` | `-` |"));
+    }
+
+    #[test]
+    fn format_benchmarks_of_layouts() {
+        let calendar = Calendar {
+            days: 2,
+            ..Calendar::default()
+        };
+        let layouts = Layouts::from([(None, (Workspace::namespaced(year!(2024)), calendar))]);
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(
+            &mut s,
+            get_mock_timings(),
+            &layouts,
+            190.0,
+            Metric::Mean,
+            &TableSettings::default(),
+        )
+        .unwrap();
+        assert!(s.contains("| [Day 2](./src/bin/2024-02.rs) | `30ms` | `40ms` |"));
+        assert!(!s.contains("Day 4"));
+    }
+
+    #[test]
+    fn format_benchmarks_by_year() {
+        let mut timings = get_mock_timings();
+        timings.data[0].year = Some(year!(2015));
+        timings.data[1].year = Some(year!(2016));
+        timings.data[2].year = Some(year!(2016));
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(
+            &mut s,
+            timings,
+            &Layouts::new(),
+            190.0,
+            Metric::Mean,
+            &TableSettings::default(),
+        )
+        .unwrap();
+        assert_eq!(s.matches("| Day | Part 1 | Part 2 |").count(), 2);
+        assert!(s.contains("### 2015\n\n| Day"));
+        assert!(s.contains("| [Day 1](./src/bin/2015-01.rs) | `10ms` | `20ms` |\n\n### 2016"));
+        assert!(s.contains("| [Day 4](./src/bin/2016-04.rs) | `40ms` | `50ms` |\n\n**Total"));
+    }
+
+    #[test]
+    fn format_configured_columns() {
+        let mut timings = get_mock_timings();
+        timings.data[0].part_1_samples = Some(100);
+
+        let settings = TableSettings {
+            columns: vec![
+                Column::Total,
+                Column::Share,
+                Column::Samples,
+                Column::Puzzle,
+                Column::Sparkline,
+            ],
+            sort: Sort::Cost,
+        };
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(
+            &mut s,
+            timings,
+            &Layouts::new(),
+            190_000.0,
+            Metric::Mean,
+            &settings,
+        )
+        .unwrap();
+
+        let rows: Vec<&str> = s.lines().filter(|l| l.starts_with("| [Day")).collect();
+        assert_eq!(
+            rows,
+            vec![
+                "| [Day 4](./src/bin/04.rs) | `90.0s` | 47.4% | `- / -` | [Puzzle](./data/puzzles/04.md) | `██████████` |",
+                "| [Day 2](./src/bin/02.rs) | `70.0s` | 36.8% | `- / -` | [Puzzle](./data/puzzles/02.md) | `███████▊  ` |",
+                "| [Day 1](./src/bin/01.rs) | `30.0s` | 15.8% | `100 / -` | [Puzzle](./data/puzzles/01.md) | `███▍      ` |",
+            ]
+        );
+        assert!(s.contains("| Day | Total | Share | Samples | Puzzle | Runtime |"));
+        assert!(s.contains("| :---: | :---: | :---: | :---: | :---: | :---: |"));
+    }
+
+    #[test]
+    fn format_parse_column() {
+        let mut timings = get_mock_timings();
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(
+            &mut s,
+            timings.clone(),
+            &Layouts::new(),
+            190_000.0,
+            Metric::Mean,
+            &TableSettings::default(),
+        )
+        .unwrap();
+        assert!(s.contains("| Day | Part 1 | Part 2 |"));
+
+        timings.data[0].parse = Some("5.0s".into());
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(
+            &mut s,
+            timings,
+            &Layouts::new(),
+            190_000.0,
+            Metric::Mean,
+            &TableSettings::default(),
+        )
+        .unwrap();
+        assert!(s.contains("| Day | Parse | Part 1 | Part 2 |"));
+        assert!(s.contains("| [Day 1](./src/bin/01.rs) | `5.0s` | `10ms` |"));
+        assert!(s.contains("| [Day 2](./src/bin/02.rs) | `-` | `30ms` |"));
+    }
 }
@@ -0,0 +1,271 @@
+/// Module that updates the readme with a table of collected stars.
+/// Unlike the `advent-readme-stars` action, progress is derived from local state only.
+use std::fs;
+
+use crate::template::answers::Answers;
+use crate::template::calendar::Calendar;
+use crate::template::readme_benchmarks::{locate_table, Error};
+use crate::template::results::ERROR_LABEL;
+use crate::template::submissions::{Outcome, Submissions};
+use crate::template::timeout::TIMEOUT_LABEL;
+use crate::template::timings::Timings;
+use crate::template::{Day, Workspace, Year};
+
+static MARKER: &str = "<!--- advent_readme_stars table --->";
+
+/// How far a part has been solved.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Progress {
+    Unsolved,
+    /// The solution returns an answer, but it was neither accepted nor recorded.
+    Answered,
+    /// The answer was accepted by the website or recorded as known-good.
+    Solved,
+}
+
+impl Progress {
+    fn as_str(self) -> &'static str {
+        match self {
+            Progress::Unsolved => " ",
+            Progress::Answered => "☆",
+            Progress::Solved => "⭐",
+        }
+    }
+}
+
+/// The progress of both parts of a day.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DayProgress {
+    pub day: Day,
+    pub part_1: Progress,
+    pub part_2: Progress,
+}
+
+/// Collect the progress of every day of the event that has been worked on.
+///
+/// A part counts as solved if an answer was recorded or a submission was accepted. Otherwise, it
+/// counts as answered if its stored timing shows that it returns an answer. Stored timings are only
+/// updated by `time --store`, so they can lag behind the solution.
+pub fn collect(
+    calendar: &Calendar,
+    answers: &Answers,
+    submissions: &Submissions,
+    timings: &Timings,
+) -> Vec<DayProgress> {
+    let progress = |day: Day, part: u8| {
+        let accepted = submissions.data.iter().any(|s| {
+            s.day == day
+                && s.part == part
+                && matches!(s.outcome, Outcome::Correct | Outcome::AlreadySolved)
+        });
+
+        if accepted || answers.get(day, part).is_some() {
+            return Progress::Solved;
+        }
+
+        let answered = timings.data.iter().any(|t| {
+            let timing = if part == 1 { &t.part_1 } else { &t.part_2 };
+            t.day == day
+                && timing
+                    .as_deref()
+                    .is_some_and(|t| t != TIMEOUT_LABEL && t != ERROR_LABEL)
+        });
+
+        if answered {
+            Progress::Answered
+        } else {
+            Progress::Unsolved
+        }
+    };
+
+    calendar
+        .days()
+        .map(|day| DayProgress {
+            day,
+            part_1: progress(day, 1),
+            part_2: progress(day, 2),
+        })
+        .filter(|p| p.part_1 != Progress::Unsolved || p.part_2 != Progress::Unsolved)
+        .collect()
+}
+
+fn construct_table(year: Option<Year>, progress: &[DayProgress], calendar: &Calendar) -> String {
+    let header = year.map_or_else(|| "## Results".into(), |year| format!("## {year} Results"));
+
+    let mut lines: Vec<String> = vec![
+        MARKER.into(),
+        header,
+        String::new(),
+        "| Day | Part 1 | Part 2 |".into(),
+        "| :---: | :---: | :---: |".into(),
+    ];
+
+    for p in progress {
+        let day = p.day.into_inner();
+        let label = match year {
+            Some(year) => format!("[Day {day}](https://adventofcode.com/{year}/day/{day})"),
+            None => format!("Day {day}"),
+        };
+
+        lines.push(format!(
+            "| {label} | {} | {} |",
+            p.part_1.as_str(),
+            p.part_2.as_str()
+        ));
+    }
+
+    let stars = progress
+        .iter()
+        .flat_map(|p| [p.part_1, p.part_2])
+        .filter(|p| *p == Progress::Solved)
+        .count();
+
+    lines.push(String::new());
+    lines.push(format!(
+        "**Stars: {stars} / {}**",
+        u32::from(calendar.days) * 2
+    ));
+    lines.push(MARKER.into());
+
+    lines.join("\n")
+}
+
+fn update_content(
+    s: &mut String,
+    year: Option<Year>,
+    progress: &[DayProgress],
+    calendar: &Calendar,
+) -> Result<(), Error> {
+    let positions = locate_table(s, MARKER)?;
+    let table = construct_table(year, progress, calendar);
+    s.replace_range(positions.pos_start..positions.pos_end, &table);
+    Ok(())
+}
+
+/// Update the stars table with the progress of a year.
+/// `timings` are the stored timings of all years.
+pub fn update(workspace: &Workspace, calendar: &Calendar, timings: &Timings) -> Result<(), Error> {
+    let answers = Answers::read_from_file(workspace);
+    let submissions = Submissions::read_from_file(workspace).map_err(Error::Parser)?;
+    let progress = collect(
+        calendar,
+        &answers,
+        &submissions,
+        &timings.for_year(workspace.year),
+    );
+
+    let path = "README.md";
+    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+    update_content(&mut readme, workspace.year, &progress, calendar)?;
+    fs::write(path, &readme)?;
+    Ok(())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{collect, update_content, DayProgress, Progress, MARKER};
+    use crate::{
+        day,
+        template::{
+            answers::Answers,
+            calendar::Calendar,
+            submissions::{Outcome, Submission, Submissions},
+            timeout::TIMEOUT_LABEL,
+            timings::{Timing, Timings},
+        },
+        year,
+    };
+
+    fn timing(day: crate::template::Day, part_1: Option<&str>, part_2: Option<&str>) -> Timing {
+        Timing {
+            part_1: part_1.map(Into::into),
+            part_2: part_2.map(Into::into),
+            ..Timing::new(None, day)
+        }
+    }
+
+    #[test]
+    fn collects_progress_from_local_state() {
+        let mut answers = Answers::default();
+        answers.record(day!(1), 1, "11");
+
+        let submissions = Submissions {
+            data: vec![
+                Submission::new(day!(1), 2, "31", Outcome::Correct),
+                Submission::new(day!(2), 1, "3", Outcome::TooLow),
+            ],
+        };
+
+        let timings = Timings {
+            data: vec![
+                timing(day!(2), Some("1.0ms"), None),
+                timing(day!(3), Some(TIMEOUT_LABEL), None),
+            ],
+        };
+
+        let progress = collect(&Calendar::default(), &answers, &submissions, &timings);
+        assert_eq!(
+            progress,
+            vec![
+                DayProgress {
+                    day: day!(1),
+                    part_1: Progress::Solved,
+                    part_2: Progress::Solved,
+                },
+                DayProgress {
+                    day: day!(2),
+                    part_1: Progress::Answered,
+                    part_2: Progress::Unsolved,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn replaces_single_marker() {
+        let progress = [DayProgress {
+            day: day!(1),
+            part_1: Progress::Solved,
+            part_2: Progress::Answered,
+        }];
+
+        let mut s = format!("foo\n{MARKER}\nbar");
+        update_content(&mut s, Some(year!(2024)), &progress, &Calendar::default()).unwrap();
+        update_content(&mut s, Some(year!(2024)), &progress, &Calendar::default()).unwrap();
+
+        let expected = [
+            "foo",
+            MARKER,
+            "## 2024 Results",
+            "",
+            "| Day | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: |",
+            "| [Day 1](https://adventofcode.com/2024/day/1) | ⭐ | ☆ |",
+            "",
+            "**Stars: 1 / 50**",
+            MARKER,
+            "bar",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn ignores_marker_in_prose() {
+        let progress = [DayProgress {
+            day: day!(1),
+            part_1: Progress::Solved,
+            part_2: Progress::Unsolved,
+        }];
+
+        let prose = format!("`cargo stars` fills the `{MARKER}` marker.");
+        let mut s = format!("foo\n{MARKER}\nbar\n{prose}\n<!--- benchmarking table --->");
+        update_content(&mut s, Some(year!(2024)), &progress, &Calendar::default()).unwrap();
+
+        assert_eq!(s.matches(MARKER).count(), 3);
+        assert!(s.starts_with(&format!("foo\n{MARKER}\n## 2024 Results")));
+        assert!(s.ends_with(&format!(
+            "{MARKER}\nbar\n{prose}\n<!--- benchmarking table --->"
+        )));
+    }
+}
@@ -0,0 +1,68 @@
+/// In-process access to solutions.
+///
+/// The `solution!` macro emits a [`Solution`] for every day. The `registry` binary collects these
+/// and runs the requested days one after another, without spawning a `cargo run` per day.
+use std::{
+    env, fs,
+    panic::{self, AssertUnwindSafe},
+};
+
+use crate::template::{run_multi::print_day_header, runner, Day, Workspace};
+
+/// A solution that can be run in-process.
+pub struct Solution {
+    pub day: Day,
+    pub workspace: Workspace,
+    /// Runs all parts of the solution against the provided input.
+    pub run: fn(&str),
+}
+
+/// Entry point of the `registry` binary.
+///
+/// Runs the days whose binary names are passed as free arguments, e.g. `01` or `2024-01`, against
+/// their inputs. Runs every registered solution if none were passed.
+/// Any other flags, e.g. `--time`, are read by the runner as usual.
+pub fn run(solutions: &[Solution]) {
+    let mut requested: Vec<(Workspace, Day)> = env::args()
+        .skip(1)
+        .filter_map(|arg| Workspace::parse_bin_name(&arg))
+        .collect();
+
+    if requested.is_empty() {
+        requested = solutions.iter().map(|s| (s.workspace, s.day)).collect();
+    }
+
+    let mut need_space = false;
+
+    requested.into_iter().for_each(|(workspace, day)| {
+        print_day_header(day, need_space);
+        need_space = true;
+
+        let Some(solution) = solutions
+            .iter()
+            .find(|s| s.day == day && s.workspace == workspace)
+        else {
+            println!("Not solved.");
+            return;
+        };
+
+        let filepath = env::current_dir()
+            .unwrap()
+            .join(workspace.data_path("inputs", &format!("{day}.txt")));
+
+        let input = match fs::read_to_string(filepath) {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("could not open input file: {e}");
+                return;
+            }
+        };
+
+        // a panicking solution should not prevent the remaining days from running.
+        if panic::catch_unwind(AssertUnwindSafe(|| (solution.run)(&input))).is_err() {
+            eprintln!("Day {day} panicked.");
+        }
+    });
+
+    runner::exit_on_regression();
+}
@@ -0,0 +1,289 @@
+/// Renders stored timings to other formats than the readme table, e.g. to feed them into a spreadsheet.
+use std::{fmt::Write, fs, io, path::PathBuf, str::FromStr};
+
+use tinyjson::JsonValue;
+
+use crate::template::readme_benchmarks::{self, locate_years, markdown_table, Metric};
+use crate::template::results::{part_label, PARSE_PART};
+use crate::template::stats::format_nanos;
+use crate::template::table_settings::TableSettings;
+use crate::template::timings::{Timing, Timings};
+
+/// The format a report is rendered in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Format {
+    /// One row per part.
+    Csv,
+    /// The contents of `data/timings.json`, pretty-printed.
+    Json,
+    /// The benchmark table of the readme as a standalone document.
+    #[default]
+    Markdown,
+    /// A self-contained page with a bar chart of all parts.
+    Html,
+}
+
+impl Format {
+    /// Guess the format from the extension of a file, e.g. `timings.csv`.
+    pub fn from_path(path: &std::path::Path) -> Option<Self> {
+        path.extension()?.to_str()?.parse().ok()
+    }
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "csv" => Ok(Format::Csv),
+            "json" => Ok(Format::Json),
+            "md" | "markdown" => Ok(Format::Markdown),
+            "html" | "htm" => Ok(Format::Html),
+            s => Err(format!(
+                "unknown report format `{s}`, expected csv, json, markdown or html."
+            )),
+        }
+    }
+}
+
+/// Where a report is written to.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum Target {
+    #[default]
+    Stdout,
+    File(PathBuf),
+    /// The benchmark table in `README.md`. Only supports the Markdown format.
+    Readme,
+}
+
+/// Render `timings` in `format`.
+pub fn render(timings: &Timings, format: Format, metric: Metric) -> Result<String, String> {
+    match format {
+        Format::Csv => Ok(to_csv(timings)),
+        Format::Json => JsonValue::from(timings.clone())
+            .format()
+            .map_err(|e| e.to_string()),
+        Format::Markdown => {
+            let mut lines = markdown_table(
+                "#",
+                timings,
+                &locate_years(timings),
+                timings.total_millis(),
+                metric,
+                &TableSettings::read_from_file(),
+            );
+            lines.push(String::new());
+            Ok(lines.join("\n"))
+        }
+        Format::Html => Ok(to_html(timings, metric)),
+    }
+}
+
+/// Render `timings` in `format` and write them to `target`.
+pub fn write(
+    timings: Timings,
+    format: Format,
+    metric: Metric,
+    target: &Target,
+) -> Result<(), String> {
+    match target {
+        Target::Readme if format != Format::Markdown => {
+            Err("the readme can only be updated with the markdown format.".into())
+        }
+        Target::Readme => readme_benchmarks::update(timings, metric)
+            .map_err(|e| format!("failed to update the readme: {e:?}")),
+        Target::Stdout => {
+            print!("{}", render(&timings, format, metric)?);
+            Ok(())
+        }
+        Target::File(path) => fs::write(path, render(&timings, format, metric)?)
+            .map_err(|e: io::Error| format!("{}: {e}", path.display())),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn to_csv(timings: &Timings) -> String {
+    let mut csv = String::from(
+        "year,day,part,time,mean_nanos,median_nanos,min_nanos,max_nanos,p95_nanos,stddev_nanos,peak_heap_bytes\n",
+    );
+
+    for timing in &timings.data {
+        for part in ["parse", "1", "2"] {
+            let (time, stats, heap) = match part {
+                "parse" => (&timing.parse, timing.parse_stats, None),
+                "1" => (&timing.part_1, timing.part_1_stats, timing.part_1_heap),
+                _ => (&timing.part_2, timing.part_2_stats, timing.part_2_heap),
+            };
+
+            let Some(time) = time else {
+                continue;
+            };
+
+            let stats = stats.map_or_else(
+                || ",,,,,".into(),
+                |s| {
+                    [s.mean, s.median, s.min, s.max, s.p95, s.stddev]
+                        .map(|x| format!("{x:.0}"))
+                        .join(",")
+                },
+            );
+
+            let _ = writeln!(
+                csv,
+                "{},{},{part},{time},{stats},{}",
+                timing.year.map(|y| y.to_string()).unwrap_or_default(),
+                timing.day,
+                heap.map(|h| h.peak_bytes.to_string()).unwrap_or_default(),
+            );
+        }
+    }
+
+    csv
+}
+
+fn to_html(timings: &Timings, metric: Metric) -> String {
+    let median = metric == Metric::Median;
+
+    let bars: Vec<(&Timing, u8, f64)> = timings
+        .data
+        .iter()
+        .flat_map(|t| [PARSE_PART, 1, 2].map(|part| (t, part, t.part_nanos(part, median))))
+        .filter_map(|(t, part, nanos)| Some((t, part, nanos?)))
+        .collect();
+
+    let max = bars.iter().map(|(_, _, nanos)| *nanos).fold(0.0, f64::max);
+
+    let mut rows = String::new();
+    for (timing, part, nanos) in &bars {
+        let part = part_label(*part).to_lowercase();
+        let label = match timing.year {
+            Some(year) => format!("{year} day {} {part}", timing.day),
+            None => format!("Day {} {part}", timing.day),
+        };
+        let width = if max > 0.0 { nanos / max * 100.0 } else { 0.0 };
+
+        let _ = writeln!(
+            rows,
+            "<tr><th>{label}</th><td><div class=\"bar\" style=\"width: {width:.2}%\"></div></td><td>{}</td></tr>",
+            format_nanos(*nanos)
+        );
+    }
+
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>Benchmarks</title>
+<style>
+body {{ font-family: sans-serif; margin: 2rem; }}
+table {{ border-collapse: collapse; width: 100%; }}
+th {{ text-align: left; white-space: nowrap; padding-right: 1rem; font-weight: normal; }}
+td:last-child {{ text-align: right; white-space: nowrap; padding-left: 1rem; font-family: monospace; }}
+.bar {{ background: #0f8b3c; height: 1rem; min-width: 1px; }}
+</style>
+</head>
+<body>
+<h1>Benchmarks</h1>
+<table>
+{rows}</table>
+<p><strong>Total: {:.2}ms</strong></p>
+</body>
+</html>
+"#,
+        timings.total_millis()
+    )
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{render, Format};
+    use crate::{
+        day,
+        template::{
+            readme_benchmarks::Metric,
+            stats::Stats,
+            timings::{Timing, Timings},
+        },
+    };
+    use std::path::Path;
+
+    fn get_timings_with_stats() -> Timings {
+        Timings {
+            data: vec![Timing {
+                part_1: Some("10.0ms".into()),
+                total_nanos: 1e+7,
+                part_1_stats: Some(Stats {
+                    mean: 10_000_000.0,
+                    median: 9_000_000.0,
+                    min: 8_000_000.0,
+                    max: 30_000_000.0,
+                    p95: 20_000_000.0,
+                    stddev: 1_500_000.0,
+                }),
+                ..Timing::new(None, day!(1))
+            }],
+        }
+    }
+
+    #[test]
+    fn parses_formats() {
+        assert_eq!("CSV".parse::<Format>(), Ok(Format::Csv));
+        assert_eq!("md".parse::<Format>(), Ok(Format::Markdown));
+        assert!("xml".parse::<Format>().is_err());
+        assert_eq!(
+            Format::from_path(Path::new("out/timings.html")),
+            Some(Format::Html)
+        );
+        assert_eq!(Format::from_path(Path::new("timings")), None);
+    }
+
+    #[test]
+    fn renders_csv() {
+        let csv = render(&get_timings_with_stats(), Format::Csv, Metric::Mean).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(
+            lines[1],
+            ",01,1,10.0ms,10000000,9000000,8000000,30000000,20000000,1500000,"
+        );
+    }
+
+    #[test]
+    fn renders_standalone_markdown() {
+        let md = render(&get_timings_with_stats(), Format::Markdown, Metric::Mean).unwrap();
+        assert!(md.starts_with("# Benchmarks\n"));
+        assert!(!md.contains("<!---"));
+        assert!(md.contains("| [Day 1](./src/bin/01.rs) | `10.0ms` | `-` |"));
+    }
+
+    #[test]
+    fn renders_html_bars() {
+        let html = render(&get_timings_with_stats(), Format::Html, Metric::Median).unwrap();
+        assert!(html.contains("<tr><th>Day 01 part 1</th>"));
+        assert!(html.contains("width: 100.00%"));
+        assert!(html.contains("9.0ms"));
+        assert!(!html.contains("part 2"));
+    }
+
+    #[test]
+    fn renders_html_parse_bars() {
+        let mut timings = get_timings_with_stats();
+        timings.data[0].parse = Some("2.0ms".into());
+
+        let html = render(&timings, Format::Html, Metric::Median).unwrap();
+        assert!(html.contains("<tr><th>Day 01 parse</th>"));
+        assert!(html.find("Day 01 parse") < html.find("Day 01 part 1"));
+        assert!(html.contains("2.0ms"));
+    }
+
+    #[test]
+    fn renders_json() {
+        let json = render(&get_timings_with_stats(), Format::Json, Metric::Mean).unwrap();
+        let timings = Timings::try_from(json).unwrap();
+        assert_eq!(timings.data.len(), 1);
+    }
+}
@@ -0,0 +1,324 @@
+/// Machine-readable results that solution binaries hand back to `run_multi`.
+///
+/// When the `AOC_RESULTS_FILE` environment variable is set, the runner appends one JSON line per part
+/// to the referenced file. This keeps the human-readable stdout free to change while callers get
+/// exact answers and timings.
+use std::{
+    collections::HashMap,
+    env, fs,
+    io::{self, Write},
+    str::FromStr,
+};
+
+use tinyjson::JsonValue;
+
+use crate::template::{
+    bench_config::{self, BenchConfig},
+    heap::{self, HeapStats},
+    stats,
+    stats::Stats,
+    Day,
+};
+
+pub const RESULTS_FILE_ENV: &str = "AOC_RESULTS_FILE";
+
+/// Shown in place of the timing of a part that failed with an error.
+pub const ERROR_LABEL: &str = "✘ error";
+
+/// The part number that results of the shared `parse` stage of a solution are recorded under.
+pub const PARSE_PART: u8 = 0;
+
+/// How a part is labeled in the output, e.g. `Part 1` or `Parse`.
+pub fn part_label(part: u8) -> String {
+    match part {
+        PARSE_PART => "Parse".into(),
+        part => format!("Part {part}"),
+    }
+}
+
+/// Outcome of running a single part.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Status {
+    Solved,
+    Unsolved,
+    /// The part was cancelled because it exceeded its timeout.
+    Timeout,
+    /// The part returned an error.
+    Error,
+}
+
+impl Status {
+    fn as_str(self) -> &'static str {
+        match self {
+            Status::Solved => "solved",
+            Status::Unsolved => "unsolved",
+            Status::Timeout => "timeout",
+            Status::Error => "error",
+        }
+    }
+}
+
+impl FromStr for Status {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "solved" => Ok(Status::Solved),
+            "unsolved" => Ok(Status::Unsolved),
+            "timeout" => Ok(Status::Timeout),
+            "error" => Ok(Status::Error),
+            s => Err(format!("unknown result status `{s}`.")),
+        }
+    }
+}
+
+/// The result of running one part of a solution.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartResult {
+    pub day: Day,
+    pub part: u8,
+    pub status: Status,
+    pub answer: Option<String>,
+    /// The error of a part that failed, along with its sources.
+    pub error: Option<String>,
+    pub nanos: f64,
+    pub samples: u64,
+    /// Full benchmark statistics, only present if the part was benched.
+    pub stats: Option<Stats>,
+    /// The settings the part was benched with, only present if the part was benched.
+    pub bench_config: Option<BenchConfig>,
+    /// Whether the answer matches the recorded answer, `None` if no answer was recorded.
+    pub verified: Option<bool>,
+    /// Allocations of the first run, only present if built with the `heap-stats` feature.
+    pub heap: Option<HeapStats>,
+}
+
+/// Append a result record to the file referenced by `AOC_RESULTS_FILE`, if set.
+pub fn emit(result: &PartResult) -> Result<(), io::Error> {
+    let Ok(path) = env::var(RESULTS_FILE_ENV) else {
+        return Ok(());
+    };
+
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+
+    let line = JsonValue::from(result)
+        .stringify()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+
+    writeln!(file, "{line}")
+}
+
+/// Parse all records from the contents of a results file. Blank lines are skipped.
+pub fn parse(contents: &str) -> Result<Vec<PartResult>, String> {
+    contents
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| {
+            let json = JsonValue::from_str(l).or(Err("result is not valid JSON."))?;
+            PartResult::try_from(&json)
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&PartResult> for JsonValue {
+    fn from(value: &PartResult) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert(
+            "status".into(),
+            JsonValue::String(value.status.as_str().into()),
+        );
+        map.insert(
+            "answer".into(),
+            match &value.answer {
+                Some(x) => JsonValue::String(x.clone()),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert(
+            "error".into(),
+            value
+                .error
+                .as_ref()
+                .map_or(JsonValue::Null, |e| JsonValue::String(e.clone())),
+        );
+        map.insert("nanos".into(), JsonValue::Number(value.nanos));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert("stats".into(), stats::to_json(value.stats.as_ref()));
+        map.insert(
+            "bench_config".into(),
+            value
+                .bench_config
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+        map.insert(
+            "verified".into(),
+            value.verified.map_or(JsonValue::Null, JsonValue::Boolean),
+        );
+        map.insert("heap".into(), heap::to_json(value.heap.as_ref()));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartResult {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected result to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected result.day to be a Day struct.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u8)
+            .ok_or("Expected result.part to be a number.")?;
+
+        let status = json
+            .get("status")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected result.status to be a string.")?
+            .parse()?;
+
+        let answer = json
+            .get("answer")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected result.answer to be null or string.")?;
+
+        // absent in results of older versions.
+        let error = match json.get("error") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(
+                v.get::<String>()
+                    .ok_or("Expected result.error to be null or string.")?
+                    .clone(),
+            ),
+        };
+
+        let nanos = json
+            .get("nanos")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected result.nanos to be a number.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let samples = json
+            .get("samples")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u64)
+            .ok_or("Expected result.samples to be a number.")?;
+
+        let stats = stats::from_json(json.get("stats"))?;
+        let bench_config = bench_config::from_json(json.get("bench_config"))?;
+        let heap = heap::from_json(json.get("heap"))?;
+
+        let verified = match json.get("verified") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(
+                *v.get::<bool>()
+                    .ok_or("Expected result.verified to be null or boolean.")?,
+            ),
+        };
+
+        Ok(PartResult {
+            day,
+            part,
+            status,
+            answer: answer.cloned(),
+            error,
+            nanos,
+            samples,
+            stats,
+            bench_config,
+            verified,
+            heap,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse, PartResult, Status};
+    use crate::{
+        day,
+        template::{bench_config::BenchConfig, heap::HeapStats, stats::Stats},
+    };
+    use tinyjson::JsonValue;
+
+    #[test]
+    fn roundtrips_results() {
+        let result = PartResult {
+            day: day!(3),
+            part: 2,
+            status: Status::Solved,
+            answer: Some("a (weird) @ answer\nwith lines".into()),
+            error: None,
+            nanos: 74_130.0,
+            samples: 100,
+            stats: Some(Stats {
+                mean: 74_130.0,
+                median: 70_000.0,
+                min: 60_000.0,
+                max: 90_000.0,
+                p95: 85_000.0,
+                stddev: 1_000.5,
+            }),
+            bench_config: Some(BenchConfig::default()),
+            verified: Some(false),
+            heap: Some(HeapStats {
+                total_bytes: 2048,
+                peak_bytes: 1024,
+                allocations: 3,
+            }),
+        };
+        let line = JsonValue::from(&result).stringify().unwrap();
+        let parsed = parse(&format!("{line}\n\n")).unwrap();
+        assert_eq!(parsed, vec![result]);
+    }
+
+    #[test]
+    fn parses_unsolved_parts() {
+        let parsed = parse(
+            r#"{ "day": "01", "part": 1, "status": "unsolved", "answer": null, "nanos": 12, "samples": 1 }"#,
+        )
+        .unwrap();
+        assert_eq!(parsed[0].status, Status::Unsolved);
+        assert_eq!(parsed[0].answer, None);
+    }
+
+    #[test]
+    fn parses_failed_parts() {
+        let parsed = parse(
+            r#"{ "day": "01", "part": 2, "status": "error", "answer": null, "error": "invalid digit", "nanos": 12, "samples": 1 }"#,
+        )
+        .unwrap();
+        assert_eq!(parsed[0].status, Status::Error);
+        assert_eq!(parsed[0].error.as_deref(), Some("invalid digit"));
+    }
+
+    #[test]
+    fn errors_for_malformed_results() {
+        assert!(parse(r#"{ "day": "01", "part": 1 }"#).is_err());
+        assert!(parse("Part 1: 42 (74.13ns @ 100 samples)").is_err());
+    }
+}
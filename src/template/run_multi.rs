@@ -1,41 +1,161 @@
-use std::{collections::HashSet, io};
+use std::{
+    collections::{BTreeMap, HashSet},
+    io, process,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+};
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{
+    bench_config::BenchConfig, calendar::Calendar, Day, Workspace, ANSI_BOLD, ANSI_ITALIC,
+    ANSI_RESET,
+};
 
 use super::{
     all_days,
+    results::{PartResult, Status},
+    timeout::Timeouts,
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
-    let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+/// Options that control how `run_multi` invokes solutions.
+#[derive(Clone, Debug, Default)]
+pub struct RunOptions {
+    pub is_release: bool,
+    pub is_timed: bool,
+    pub bench: BenchConfig,
+    /// Spawn one `cargo run` per day instead of running all days in the `registry` binary.
+    pub subprocess: bool,
+    /// Number of days to run concurrently. Timed runs are always run serially.
+    pub jobs: usize,
+    /// Wall-clock limits for each part, enforced by the solutions themselves.
+    pub timeouts: Timeouts,
+    /// Build with the `heap-stats` feature to collect allocations of each part.
+    pub heap: bool,
+    /// The year to run the solutions of.
+    pub workspace: Workspace,
+    /// The days of the event, run when no day is selected.
+    pub calendar: Calendar,
+}
 
-    let mut need_space = false;
+/// The outcome of running a set of days.
+pub struct RunSummary {
+    /// Timings of all days that were run, only present for timed runs.
+    pub timings: Option<Timings>,
+    /// The results of all parts that were run.
+    pub results: Vec<PartResult>,
+}
 
+impl RunSummary {
+    /// Parts that returned a different answer than the recorded one.
+    pub fn regressions(&self) -> Vec<&PartResult> {
+        self.results
+            .iter()
+            .filter(|r| r.verified == Some(false))
+            .collect()
+    }
+
+    /// Report regressed parts and exit with a non-zero status if there are any.
+    pub fn exit_on_regression(&self) {
+        let regressions = self.regressions();
+
+        if regressions.is_empty() {
+            return;
+        }
+
+        eprintln!();
+        for r in &regressions {
+            eprintln!(
+                "✘ Day {} part {} no longer returns the recorded answer.",
+                r.day, r.part
+            );
+        }
+        process::exit(1);
+    }
+}
+
+pub fn run_multi(days_to_run: &HashSet<Day>, options: &RunOptions) -> RunSummary {
     // NOTE: use non-duplicate, sorted day values.
-    all_days()
-        .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
+    let days: Vec<Day> = all_days().filter(|day| days_to_run.contains(day)).collect();
+
+    let mut results: Vec<PartResult> = vec![];
+
+    let timings: Vec<Timing> = if options.jobs > 1 && !options.is_timed {
+        let mut timings = Vec::with_capacity(days.len());
+
+        run_parallel(&days, options, |i, day, output| {
+            if options.subprocess {
+                print_day_header(day, i > 0);
+            } else if i > 0 {
                 println!();
             }
-            need_space = true;
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+            match output {
+                None => println!("Not solved."),
+                Some(output) => {
+                    output.print();
+                    if !output.results.is_empty() || options.subprocess {
+                        timings.push(child_commands::collect_timing(&output.results, day));
+                    }
+                    results.extend(output.results);
+                }
+            }
+        });
+
+        timings
+    } else if options.subprocess {
+        let mut timings = Vec::with_capacity(days.len());
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+        for (i, day) in days.iter().enumerate() {
+            print_day_header(*day, i > 0);
 
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
+            match child_commands::run_solution(*day, options, false).unwrap() {
+                None => println!("Not solved."),
+                Some(output) => {
+                    timings.push(child_commands::collect_timing(&output.results, *day));
+                    results.extend(output.results);
+                }
             }
-        });
+        }
+
+        timings
+    } else {
+        let mut remaining = &days[..];
+
+        while !remaining.is_empty() {
+            let output = child_commands::run_registry(remaining, options, false).unwrap();
+
+            // a timeout ends the registry process, resume with the days after the timed out one.
+            let timed_out = output
+                .results
+                .iter()
+                .find(|r| r.status == Status::Timeout)
+                .map(|r| r.day);
+
+            results.extend(output.results);
 
-    if is_timed {
-        let timings = Timings { data: timings };
+            let Some(timed_out) = timed_out else {
+                break;
+            };
+
+            remaining = &remaining[remaining.iter().position(|d| *d == timed_out).unwrap() + 1..];
+
+            if !remaining.is_empty() {
+                println!();
+            }
+        }
+
+        days.iter()
+            .filter(|day| results.iter().any(|r| r.day == **day))
+            .map(|day| child_commands::collect_timing(&results, *day))
+            .collect()
+    };
+
+    let timings = if options.is_timed {
+        let mut timings = Timings { data: timings };
+        timings.assign_year(options.workspace.year);
         let total_millis = timings.total_millis();
         println!(
             "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
@@ -43,7 +163,67 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
         Some(timings)
     } else {
         None
+    };
+
+    RunSummary { timings, results }
+}
+
+/// Run days on `options.jobs` worker threads, each spawning one child per day with buffered output.
+/// `on_done` is called in day order as soon as a day and all days before it have finished.
+fn run_parallel(
+    days: &[Day],
+    options: &RunOptions,
+    mut on_done: impl FnMut(usize, Day, Option<child_commands::ChildOutput>),
+) {
+    let next = AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..options.jobs.min(days.len()) {
+            let tx = tx.clone();
+            let next = &next;
+
+            scope.spawn(move || loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                let Some(day) = days.get(i) else {
+                    break;
+                };
+
+                let output = if options.subprocess {
+                    child_commands::run_solution(*day, options, true).unwrap()
+                } else {
+                    Some(child_commands::run_registry(&[*day], options, true).unwrap())
+                };
+
+                tx.send((i, output)).unwrap();
+            });
+        }
+
+        drop(tx);
+
+        // buffer finished days until all days before them are done.
+        let mut pending = BTreeMap::new();
+        let mut current = 0;
+
+        for (i, output) in rx {
+            pending.insert(i, output);
+
+            while let Some(output) = pending.remove(&current) {
+                on_done(current, days[current], output);
+                current += 1;
+            }
+        }
+    });
+}
+
+/// Print the heading that precedes the output of a day.
+pub fn print_day_header(day: Day, need_space: bool) {
+    if need_space {
+        println!();
     }
+
+    println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+    println!("------");
 }
 
 #[allow(dead_code)]
@@ -51,6 +231,7 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
 pub enum Error {
     BrokenPipe,
     IO(io::Error),
+    Results(String),
 }
 
 impl From<std::io::Error> for Error {
@@ -59,48 +240,132 @@ impl From<std::io::Error> for Error {
     }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
 /// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+/// This module encapsulates interaction with these binaries, both invoking them as well as collecting their results.
 pub mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use super::{Error, RunOptions};
+    use crate::template::{
+        results::{self, PartResult, Status, ERROR_LABEL, PARSE_PART, RESULTS_FILE_ENV},
+        stats::format_nanos,
+        timeout::TIMEOUT_LABEL,
+        Day,
+    };
     use std::{
+        env, fs,
         io::{BufRead, BufReader},
         path::Path,
-        process::{Command, Stdio},
+        process::{self, Command, Stdio},
         thread,
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    /// The output of a child command.
+    pub struct ChildOutput {
+        pub results: Vec<PartResult>,
+        /// Lines printed by the child, only collected if its output was buffered.
+        pub stdout: Vec<String>,
+        pub stderr: Vec<String>,
+    }
+
+    impl ChildOutput {
+        /// Print buffered output.
+        pub fn print(&self) {
+            self.stdout.iter().for_each(|line| println!("{line}"));
+            self.stderr.iter().for_each(|line| eprintln!("{line}"));
+        }
+    }
+
+    /// Run the solution bin for a given day and return the results it reported.
+    /// Returns `None` if the day has not been scaffolded yet.
+    /// If `buffered` is set, output is collected instead of forwarded.
+    pub fn run_solution(
+        day: Day,
+        options: &RunOptions,
+        buffered: bool,
+    ) -> Result<Option<ChildOutput>, Error> {
         // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+        if !Path::new(&options.workspace.bin_path(day)).exists() {
+            return Ok(None);
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        let bin_name = options.workspace.bin_name(day);
+        let mut args = vec!["run", "--quiet", "--bin", &bin_name];
 
-        if is_release {
+        if options.is_release {
             args.push("--release");
         }
 
-        if is_timed {
+        if options.heap {
+            args.extend(["--features", "heap-stats"]);
+        }
+
+        if options.is_timed {
             // mirror `--time` flag to child invocations.
             args.push("--");
             args.push("--time");
         }
 
+        run_child(&args, options, &bin_name, buffered).map(Some)
+    }
+
+    /// Run the given days in a single invocation of the `registry` binary and return the results of all days.
+    pub fn run_registry(
+        days: &[Day],
+        options: &RunOptions,
+        buffered: bool,
+    ) -> Result<ChildOutput, Error> {
+        let day_args: Vec<String> = days
+            .iter()
+            .map(|day| options.workspace.bin_name(*day))
+            .collect();
+        let features = if options.heap {
+            "registry,heap-stats"
+        } else {
+            "registry"
+        };
+
+        let mut args = vec![
+            "run",
+            "--quiet",
+            "--features",
+            features,
+            "--bin",
+            "registry",
+        ];
+
+        if options.is_release {
+            args.push("--release");
+        }
+
+        args.push("--");
+        args.extend(day_args.iter().map(String::as_str));
+
+        if options.is_timed {
+            args.push("--time");
+        }
+
+        let label = format!("registry-{}", day_args.join("-"));
+        run_child(&args, options, &label, buffered)
+    }
+
+    /// Spawn a `cargo` child command, forward its output and collect the results it reported.
+    fn run_child(
+        args: &[&str],
+        options: &RunOptions,
+        label: &str,
+        buffered: bool,
+    ) -> Result<ChildOutput, Error> {
+        let results_path =
+            env::temp_dir().join(format!("aoc-results-{}-{label}.jsonl", process::id()));
+        let _ = fs::remove_file(&results_path);
+
         // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        // forward output to stdout/stderr, results are collected through the results file.
 
         let mut cmd = Command::new("cargo")
-            .args(&args)
+            .args(args)
+            .env(RESULTS_FILE_ENV, &results_path)
+            .envs(options.bench.env_vars())
+            .envs(options.timeouts.env_vars())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .spawn()?;
@@ -108,85 +373,88 @@ pub mod child_commands {
         let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
         let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
 
-        let mut output = vec![];
-
         let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
+            let mut lines = vec![];
+            for line in stderr.lines() {
+                let line = line.unwrap();
+                if buffered {
+                    lines.push(line);
+                } else {
+                    eprintln!("{line}");
+                }
+            }
+            lines
         });
 
+        let mut stdout_lines = vec![];
         for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+            let line = line?;
+            if buffered {
+                stdout_lines.push(line);
+            } else {
+                println!("{line}");
+            }
         }
 
-        thread.join().unwrap();
-        cmd.wait()?;
+        let stderr_lines = thread.join().unwrap();
+        let status = cmd.wait()?;
+
+        let contents = fs::read_to_string(&results_path).unwrap_or_default();
+        let _ = fs::remove_file(&results_path);
+
+        if !status.success() && contents.is_empty() && !options.subprocess {
+            eprintln!(
+                "The registry binary failed. If a single solution does not compile, use `--subprocess` to run days in isolation."
+            );
+        }
 
-        Ok(output)
+        Ok(ChildOutput {
+            results: results::parse(&contents).map_err(Error::Results)?,
+            stdout: stdout_lines,
+            stderr: stderr_lines,
+        })
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
-        let mut timings = super::Timing {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
-        };
+    /// Collect the timings of all solved parts of a day.
+    /// Parts that timed out or failed are labeled as such and do not count towards the total.
+    /// Parsing the input counts towards the total, as it is part of the runtime of the day.
+    pub fn collect_timing(results: &[PartResult], day: Day) -> super::Timing {
+        let mut timing = super::Timing::new(None, day);
 
-        output
+        results
             .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
+            .filter(|r| r.day == day && r.status != Status::Unsolved)
+            .for_each(|r| {
+                let timing_str = Some(match r.status {
+                    Status::Timeout => TIMEOUT_LABEL.into(),
+                    Status::Error => ERROR_LABEL.into(),
+                    _ => format_nanos(r.nanos),
+                });
+                // the runtime of a failed part says nothing about the solution.
+                let stats = r.stats.filter(|_| r.status == Status::Solved);
+
+                match r.part {
+                    PARSE_PART => (timing.parse, timing.parse_stats) = (timing_str, stats),
+                    1 => {
+                        (timing.part_1, timing.part_1_stats) = (timing_str, stats);
+                        timing.part_1_heap = r.heap;
+                        timing.part_1_samples = stats.map(|_| r.samples);
+                    }
+                    2 => {
+                        (timing.part_2, timing.part_2_stats) = (timing_str, stats);
+                        timing.part_2_heap = r.heap;
+                        timing.part_2_samples = stats.map(|_| r.samples);
+                    }
+                    _ => return,
                 }
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
-
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
+                if r.status == Status::Solved {
+                    timing.bench_config = timing.bench_config.or(r.bench_config);
+                    timing.total_nanos += r.nanos;
                 }
-
-                timings.total_nanos += nanos;
             });
 
-        timings
-    }
-
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
-
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
+        timing
     }
 
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
@@ -205,47 +473,66 @@ pub mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::collect_timing;
 
-        use crate::day;
+        use crate::{
+            day,
+            template::{
+                results::{PartResult, Status, ERROR_LABEL},
+                timeout::TIMEOUT_LABEL,
+            },
+        };
+
+        fn result(part: u8, status: Status, answer: &str, nanos: f64) -> PartResult {
+            PartResult {
+                day: day!(1),
+                part,
+                status,
+                answer: Some(answer.into()),
+                error: None,
+                nanos,
+                samples: 100,
+                stats: None,
+                bench_config: None,
+                verified: None,
+                heap: None,
+            }
+        }
 
         #[test]
-        fn parses_execution_times() {
-            let res = parse_exec_time(
+        fn collects_execution_times() {
+            let res = collect_timing(
                 &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
+                    result(1, Status::Solved, "0", 74.0),
+                    result(2, Status::Solved, "10", 74_130_000.0),
                 ],
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_approx_eq!(res.total_nanos, 74130074_f64);
+            assert_eq!(res.part_1.unwrap(), "74.0ns");
+            assert_eq!(res.part_2.unwrap(), "74.1ms");
         }
 
         #[test]
-        fn parses_with_patterns_in_input() {
-            let res = parse_exec_time(
+        fn collects_with_patterns_in_answer() {
+            let res = collect_timing(
                 &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
+                    result(1, Status::Solved, "@ @ @ ( ) ms", 2_000_000_000.0),
+                    result(2, Status::Solved, "10s\n(1 samples)", 100_000_000.0),
                 ],
                 day!(1),
             );
             assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+            assert_eq!(res.part_1.unwrap(), "2.0s");
+            assert_eq!(res.part_2.unwrap(), "100.0ms");
         }
 
         #[test]
-        fn parses_missing_parts() {
-            let res = parse_exec_time(
+        fn collects_missing_parts() {
+            let res = collect_timing(
                 &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
+                    result(1, Status::Unsolved, "", 10.0),
+                    result(2, Status::Unsolved, "", 10.0),
                 ],
                 day!(1),
             );
@@ -253,5 +540,47 @@ pub mod child_commands {
             assert_eq!(res.part_1.is_none(), true);
             assert_eq!(res.part_2.is_none(), true);
         }
+
+        #[test]
+        fn collects_timed_out_parts() {
+            let res = collect_timing(
+                &[
+                    result(1, Status::Solved, "0", 74.0),
+                    result(2, Status::Timeout, "", 5_000_000_000.0),
+                ],
+                day!(1),
+            );
+            assert_approx_eq!(res.total_nanos, 74_f64);
+            assert_eq!(res.part_1.unwrap(), "74.0ns");
+            assert_eq!(res.part_2.unwrap(), TIMEOUT_LABEL);
+        }
+
+        #[test]
+        fn collects_parse_times() {
+            let res = collect_timing(
+                &[
+                    result(0, Status::Solved, "", 26.0),
+                    result(1, Status::Solved, "0", 74.0),
+                ],
+                day!(1),
+            );
+            assert_approx_eq!(res.total_nanos, 100_f64);
+            assert_eq!(res.parse.unwrap(), "26.0ns");
+            assert_eq!(res.part_1.unwrap(), "74.0ns");
+        }
+
+        #[test]
+        fn collects_failed_parts() {
+            let res = collect_timing(
+                &[
+                    result(1, Status::Error, "", 10.0),
+                    result(2, Status::Solved, "10", 74.0),
+                ],
+                day!(1),
+            );
+            assert_approx_eq!(res.total_nanos, 74_f64);
+            assert_eq!(res.part_1.unwrap(), ERROR_LABEL);
+            assert_eq!(res.part_2.unwrap(), "74.0ns");
+        }
     }
 }
@@ -1,67 +1,347 @@
 /// Encapsulates code that interacts with solution functions.
+use std::error::Error;
 use std::fmt::Display;
 use std::hint::black_box;
 use std::io::{stdout, Write};
-use std::process::Output;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use std::thread;
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{env, process};
 
+use crate::template::answers::{Answers, Verification};
+use crate::template::aoc_client::{AocError, Client};
+use crate::template::bench_config::BenchConfig;
+use crate::template::heap::{self, HeapStats};
+use crate::template::input;
+use crate::template::results::{self, part_label, PartResult, Status, PARSE_PART};
+use crate::template::stats::Stats;
+use crate::template::submissions::{self, Outcome, Submission, Submissions};
+use crate::template::timeout::{Timeouts, TIMEOUT_EXIT_CODE, TIMEOUT_LABEL};
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{Day, Workspace, ANSI_ITALIC, ANSI_RESET};
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
-    let part_str = format!("Part {part}");
+/// Set when a part returned a different answer than the one recorded in `data/answers.json`.
+static HAS_REGRESSION: AtomicBool = AtomicBool::new(false);
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+/// What a part of a solution returned.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Answer {
+    Solved(String),
+    /// The part is not implemented yet.
+    Unsolved,
+    /// The part failed with an error, e.g. because the input could not be parsed.
+    Failed(String),
+}
+
+impl Answer {
+    fn solved(&self) -> Option<&str> {
+        match self {
+            Answer::Solved(answer) => Some(answer),
+            Answer::Unsolved | Answer::Failed(_) => None,
+        }
+    }
+}
+
+/// Types that parts of a solution can return: answers like `u64` or `String`, `Option<T>` for parts
+/// that are not implemented yet and `Result<T, E>` for parts that can fail.
+pub trait IntoAnswer {
+    fn into_answer(self) -> Answer;
+}
+
+macro_rules! impl_into_answer {
+    ($($t:ty),*) => {
+        $(
+            impl IntoAnswer for $t {
+                fn into_answer(self) -> Answer {
+                    Answer::Solved(self.to_string())
+                }
+            }
+        )*
+    };
+}
+
+impl_into_answer!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64, bool, char, String,
+    &str
+);
+
+impl<T: Display> IntoAnswer for Option<T> {
+    fn into_answer(self) -> Answer {
+        match self {
+            Some(answer) => Answer::Solved(answer.to_string()),
+            None => Answer::Unsolved,
+        }
+    }
+}
+
+impl<T: Display, E: Into<Box<dyn Error>>> IntoAnswer for Result<T, E> {
+    fn into_answer(self) -> Answer {
+        match self {
+            Ok(answer) => Answer::Solved(answer.to_string()),
+            Err(e) => Answer::Failed(error_chain(&*e.into())),
+        }
+    }
+}
+
+/// Format an error along with its sources, e.g. `invalid input: invalid digit found in string`.
+fn error_chain(error: &dyn Error) -> String {
+    let mut message = error.to_string();
+    let mut source = error.source();
+
+    while let Some(e) = source {
+        message.push_str(&format!(": {e}"));
+        source = e.source();
+    }
+
+    message
+}
+
+pub fn run_part<I: Clone, T: IntoAnswer>(
+    func: impl Fn(I) -> T,
+    input: I,
+    workspace: Workspace,
+    day: Day,
+    part: u8,
+) {
+    let part_str = part_label(part);
+
+    let watchdog = Timeouts::from_env()
+        .for_day(day)
+        .map(|timeout| start_watchdog(timeout, day, part));
+
+    let (answer, measurement) = run_timed(
+        func,
+        input,
+        |result| {
+            // the part finished in time, stop the watchdog.
+            drop(watchdog);
+            let answer = result.into_answer();
+            print_result(&answer, &part_str, "");
+            answer
+        },
+        // the runtime of a failed part says nothing about the solution.
+        |answer| !matches!(answer, Answer::Failed(_)),
+    );
+
+    // recorded answers only apply to the puzzle input.
+    let is_puzzle = input::Source::from_args().is_puzzle();
+
+    let verification = answer
+        .solved()
+        .filter(|_| is_puzzle)
+        .map(|result| Answers::read_from_file(&workspace).verify(day, part, result));
+
+    print_result(
+        &answer,
+        &part_str,
+        &format!(
+            "{}{}",
+            format_verification(verification.as_ref()),
+            format_duration(&measurement.duration, measurement.samples)
+        ),
+    );
+
+    if let Some((Some(stats), _)) = &measurement.bench {
+        println!("        {ANSI_ITALIC}{stats}{ANSI_RESET}");
+    }
+
+    if let Some(heap) = &measurement.heap {
+        println!("        {ANSI_ITALIC}{heap}{ANSI_RESET}");
+    }
+
+    if let Some(Verification::Incorrect { .. }) = verification {
+        HAS_REGRESSION.store(true, Ordering::Relaxed);
+    }
+
+    emit_result(&answer, verification.as_ref(), &measurement, day, part);
+
+    if let Some(result) = answer.solved() {
+        submit_result(result, &workspace, day, part);
+    }
+}
+
+/// Run the shared `parse` stage of a solution and time it like a part.
+/// The parsed input is handed to both parts, so that parsing is not timed as part of them.
+pub fn run_parse<T>(func: impl Fn(&str) -> T, input: &str, day: Day) -> T {
+    let label = part_label(PARSE_PART);
+
+    let watchdog = Timeouts::from_env()
+        .for_day(day)
+        .map(|timeout| start_watchdog(timeout, day, PARSE_PART));
+
+    let (parsed, measurement) = run_timed(
+        func,
+        input,
+        |parsed| {
+            drop(watchdog);
+            print!("{label}:");
+            parsed
+        },
+        |_| true,
+    );
+
+    print!("\r");
+    println!(
+        "{label}:{}",
+        format_duration(&measurement.duration, measurement.samples)
+    );
+
+    if let Some((Some(stats), _)) = &measurement.bench {
+        println!("        {ANSI_ITALIC}{stats}{ANSI_RESET}");
+    }
+
+    if let Some(heap) = &measurement.heap {
+        println!("        {ANSI_ITALIC}{heap}{ANSI_RESET}");
+    }
+
+    #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+    let record = PartResult {
+        day,
+        part: PARSE_PART,
+        status: Status::Solved,
+        answer: None,
+        error: None,
+        nanos: measurement.duration.as_nanos() as f64,
+        samples: measurement.samples as u64,
+        stats: measurement.bench.and_then(|(stats, _)| stats),
+        bench_config: measurement.bench.map(|(_, config)| config),
+        verified: None,
+        heap: measurement.heap,
+    };
+
+    if let Err(e) = results::emit(&record) {
+        eprintln!("Failed to write result record: {e}");
+    }
+
+    parsed
+}
+
+/// Cancel the process if the part does not finish within `timeout`.
+/// The returned sender stops the watchdog when it is dropped.
+fn start_watchdog(timeout: Duration, day: Day, part: u8) -> mpsc::Sender<()> {
+    let (tx, rx) = mpsc::channel::<()>();
+
+    thread::spawn(move || {
+        if let Err(mpsc::RecvTimeoutError::Timeout) = rx.recv_timeout(timeout) {
+            print!("\r");
+            println!("{}: {TIMEOUT_LABEL} ({timeout:?})       ", part_label(part));
+
+            #[allow(clippy::cast_precision_loss)]
+            let record = PartResult {
+                day,
+                part,
+                status: Status::Timeout,
+                answer: None,
+                error: None,
+                nanos: timeout.as_nanos() as f64,
+                samples: 0,
+                stats: None,
+                bench_config: None,
+                verified: None,
+                heap: None,
+            };
+
+            if let Err(e) = results::emit(&record) {
+                eprintln!("Failed to write result record: {e}");
+            }
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+            let _ = stdout().flush();
+            process::exit(TIMEOUT_EXIT_CODE);
+        }
+    });
+
+    tx
+}
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+/// Exit with a non-zero status if any part regressed from its recorded answer.
+pub fn exit_on_regression() {
+    if HAS_REGRESSION.load(Ordering::Relaxed) {
+        process::exit(1);
     }
 }
 
+/// What was measured while running a part.
+struct Measurement {
+    duration: Duration,
+    samples: u128,
+    /// Allocations of the first run, only collected with the `heap-stats` feature.
+    heap: Option<HeapStats>,
+    /// Statistics and settings, only present if the part was benched.
+    bench: Option<(Option<Stats>, BenchConfig)>,
+}
+
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
-fn run_timed<I: Clone, T>(
+///  2. with `--time`, the function is benched according to the [`BenchConfig`] read from the environment.
+///     By default, this is approx. 1 second of execution time or 10 samples, whatever take longer.
+///     Results that `should_bench` rejects are not benched.
+fn run_timed<I: Clone, T, R>(
     func: impl Fn(I) -> T,
     input: I,
-    hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+    hook: impl FnOnce(T) -> R,
+    should_bench: impl FnOnce(&R) -> bool,
+) -> (R, Measurement) {
     let timer = Instant::now();
-    let result = {
+    let (result, heap) = {
         let input = input.clone();
 
         #[cfg(feature = "dhat-heap")]
         let _profiler = dhat::Profiler::new_heap();
 
-        func(input)
+        heap::measure(|| func(input))
     };
     let base_time = timer.elapsed();
 
-    hook(&result);
+    let result = hook(result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
-    } else {
-        (base_time, 1)
+    if !std::env::args().any(|x| x == "--time") || !should_bench(&result) {
+        let measurement = Measurement {
+            duration: base_time,
+            samples: 1,
+            heap,
+            bench: None,
+        };
+        return (result, measurement);
+    }
+
+    let config = match BenchConfig::from_env() {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("\nInvalid benchmark settings: {e}");
+            process::exit(1);
+        }
     };
 
-    (result, run.0, run.1)
+    let (duration, samples, stats) = bench(func, input, &base_time, &config);
+    let measurement = Measurement {
+        duration,
+        samples,
+        heap,
+        bench: Some((stats, config)),
+    };
+    (result, measurement)
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
+fn bench<I: Clone, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+    config: &BenchConfig,
+) -> (Duration, u128, Option<Stats>) {
     let mut stdout = stdout();
 
     print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
     let _ = stdout.flush();
 
-    let bench_iterations =
-        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+    for _ in 0..config.warmup {
+        black_box(func(black_box(input.clone())));
+    }
+
+    let bench_iterations = config.iterations(base_time);
 
-    let mut timers: Vec<Duration> = vec![];
+    let mut timers: Vec<Duration> =
+        Vec::with_capacity(usize::try_from(bench_iterations).unwrap_or(0));
 
     for _ in 0..bench_iterations {
         // need a clone here to make the borrow checker happy.
@@ -71,19 +351,63 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         timers.push(timer.elapsed());
     }
 
+    let stats = Stats::from_samples(&timers);
+
     (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Duration::from_nanos(stats.map_or(0.0, |s| s.mean).round() as u64),
+        u128::from(bench_iterations),
+        stats,
     )
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+/// Hand the result of a part to the caller through the structured results channel.
+fn emit_result(
+    answer: &Answer,
+    verification: Option<&Verification>,
+    measurement: &Measurement,
+    day: Day,
+    part: u8,
+) {
+    #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+    let record = PartResult {
+        day,
+        part,
+        status: match answer {
+            Answer::Solved(_) => Status::Solved,
+            Answer::Unsolved => Status::Unsolved,
+            Answer::Failed(_) => Status::Error,
+        },
+        answer: answer.solved().map(str::to_string),
+        error: match answer {
+            Answer::Failed(error) => Some(error.clone()),
+            Answer::Solved(_) | Answer::Unsolved => None,
+        },
+        nanos: measurement.duration.as_nanos() as f64,
+        samples: measurement.samples as u64,
+        stats: measurement.bench.and_then(|(stats, _)| stats),
+        bench_config: measurement.bench.map(|(_, config)| config),
+        verified: match verification {
+            Some(Verification::Correct) => Some(true),
+            Some(Verification::Incorrect { .. }) => Some(false),
+            Some(Verification::Unknown) | None => None,
+        },
+        heap: measurement.heap,
+    };
+
+    if let Err(e) = results::emit(&record) {
+        eprintln!("Failed to write result record: {e}");
+    }
+}
+
+fn format_verification(verification: Option<&Verification>) -> String {
+    match verification {
+        Some(Verification::Correct) => " ✔".into(),
+        Some(Verification::Incorrect { expected }) => {
+            format!(" ✘ {ANSI_ITALIC}(expected {expected}){ANSI_RESET}")
+        }
+        Some(Verification::Unknown) | None => String::new(),
+    }
 }
 
 fn format_duration(duration: &Duration, samples: u128) -> String {
@@ -94,12 +418,12 @@ fn format_duration(duration: &Duration, samples: u128) -> String {
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+fn print_result(answer: &Answer, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
-    match result {
-        Some(result) => {
-            if result.to_string().contains('\n') {
+    match answer {
+        Answer::Solved(result) => {
+            if result.contains('\n') {
                 let str = format!("{part}: ▼ {duration_str}");
                 if is_intermediate_result {
                     print!("{str}");
@@ -118,7 +442,7 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 }
             }
         }
-        None => {
+        Answer::Unsolved => {
             if is_intermediate_result {
                 print!("{part}: ✖");
             } else {
@@ -126,21 +450,24 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 println!("{part}: ✖             ");
             }
         }
+        Answer::Failed(error) => {
+            if is_intermediate_result {
+                print!("{part}: error");
+            } else {
+                // failed parts are not benched, their runtime is not shown.
+                print!("\r");
+                println!("{part}: error: {error}");
+            }
+        }
     }
 }
 
-/// Parse the arguments passed to `solve` and try to submit one part of the solution if:
-///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
-    day: Day,
-    part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+/// Parse the arguments passed to `solve` and try to submit one part of the solution.
+fn submit_result(result: &str, workspace: &Workspace, day: Day, part: u8) {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
-        return None;
+        return;
     }
 
     if args.len() < 3 {
@@ -148,6 +475,11 @@ fn submit_result<T: Display>(
         process::exit(1);
     }
 
+    if !input::Source::from_args().is_puzzle() {
+        eprintln!("Not submitting: only results for the puzzle input can be submitted.");
+        process::exit(1);
+    }
+
     let part_index = args.iter().position(|x| x == "--submit").unwrap() + 1;
 
     let Ok(part_submit) = args[part_index].parse::<u8>() else {
@@ -156,14 +488,102 @@ fn submit_result<T: Display>(
     };
 
     if part_submit != part {
-        return None;
+        return;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+    let answer = result.to_string();
+
+    let submissions = match Submissions::read_from_file(workspace) {
+        Ok(submissions) => submissions,
+        Err(e) => {
+            eprintln!("Failed to read submission log: {e}");
+            process::exit(1);
+        }
+    };
+
+    if let Err(reason) = submissions.check(day, part, &answer) {
+        eprintln!("Not submitting `{answer}`: {reason}");
         process::exit(1);
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    println!("Submitting result...");
+
+    let client = Client::from_env(workspace.year);
+    let message = match client.and_then(|client| client.submit(day, part, &answer)) {
+        Ok(message) | Err(AocError::RateLimited { message }) => message,
+        Err(e) => {
+            eprintln!("Failed to submit result: {e}");
+            process::exit(1);
+        }
+    };
+
+    println!("{message}");
+
+    let Some(outcome) = Outcome::from_response(&message) else {
+        eprintln!("Could not interpret the response, the submission was not logged.");
+        return;
+    };
+
+    println!("{ANSI_BOLD}{outcome}{ANSI_RESET}");
+
+    if let Err(e) = submissions::append(workspace, &Submission::new(day, part, &answer, outcome)) {
+        eprintln!("Failed to write submission log: {e}");
+    }
+
+    if outcome == Outcome::Correct {
+        let mut answers = Answers::read_from_file(workspace);
+        answers.record(day, part, &answer);
+        if let Err(e) = answers.store_file(workspace) {
+            eprintln!("Failed to record answer: {e}");
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Answer, IntoAnswer};
+
+    #[test]
+    fn converts_answers() {
+        assert_eq!(42_u64.into_answer(), Answer::Solved("42".into()));
+        assert_eq!("abc".into_answer(), Answer::Solved("abc".into()));
+        assert_eq!(2.5_f64.into_answer(), Answer::Solved("2.5".into()));
+        assert_eq!(true.into_answer(), Answer::Solved("true".into()));
+        assert_eq!(Some(7_i32).into_answer(), Answer::Solved("7".into()));
+        assert_eq!(None::<u32>.into_answer(), Answer::Unsolved);
+        assert_eq!(
+            Ok::<_, String>(3_usize).into_answer(),
+            Answer::Solved("3".into())
+        );
+        assert_eq!(
+            Err::<u32, _>("missing grid").into_answer(),
+            Answer::Failed("missing grid".into())
+        );
+    }
+
+    #[test]
+    fn formats_error_chains() {
+        #[derive(Debug)]
+        struct ParseError(std::num::ParseIntError);
+
+        impl std::fmt::Display for ParseError {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "invalid input")
+            }
+        }
+
+        impl std::error::Error for ParseError {
+            fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+                Some(&self.0)
+            }
+        }
+
+        let result: Result<u32, _> = "x".parse::<u32>().map_err(ParseError);
+        assert_eq!(
+            result.into_answer(),
+            Answer::Failed("invalid input: invalid digit found in string".into())
+        );
+    }
 }
@@ -0,0 +1,171 @@
+/// Summary statistics over the samples collected while benching a part.
+use std::{collections::HashMap, fmt::Display, time::Duration};
+
+use tinyjson::JsonValue;
+
+/// Benchmark statistics for a single part. All values are in nanoseconds.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Stats {
+    pub mean: f64,
+    pub median: f64,
+    pub min: f64,
+    pub max: f64,
+    pub p95: f64,
+    pub stddev: f64,
+}
+
+impl Stats {
+    /// Compute statistics for a set of samples. Returns `None` if there are no samples.
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        let mut nanos: Vec<f64> = samples.iter().map(|d| d.as_nanos() as f64).collect();
+        nanos.sort_unstable_by(f64::total_cmp);
+
+        #[allow(clippy::cast_precision_loss)]
+        let len = nanos.len() as f64;
+        let mean = nanos.iter().sum::<f64>() / len;
+        let variance = nanos.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / len;
+
+        Some(Self {
+            mean,
+            median: percentile(&nanos, 50.0),
+            min: nanos[0],
+            max: nanos[nanos.len() - 1],
+            p95: percentile(&nanos, 95.0),
+            stddev: variance.sqrt(),
+        })
+    }
+}
+
+/// Percentile of sorted values, interpolating linearly between the closest ranks.
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    #[allow(clippy::cast_precision_loss)]
+    let rank = p / 100.0 * (sorted.len() - 1) as f64;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let (lower, upper) = (rank.floor() as usize, rank.ceil() as usize);
+
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - rank.floor())
+}
+
+/// Format nanoseconds the same way durations are displayed elsewhere, e.g. `74.1µs`.
+pub fn format_nanos(nanos: f64) -> String {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let duration = Duration::from_nanos(nanos.round() as u64);
+    format!("{duration:.1?}")
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "min {} · median {} · p95 {} · max {} · σ {}",
+            format_nanos(self.min),
+            format_nanos(self.median),
+            format_nanos(self.p95),
+            format_nanos(self.max),
+            format_nanos(self.stddev)
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Stats> for JsonValue {
+    fn from(value: &Stats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("mean".into(), JsonValue::Number(value.mean));
+        map.insert("median".into(), JsonValue::Number(value.median));
+        map.insert("min".into(), JsonValue::Number(value.min));
+        map.insert("max".into(), JsonValue::Number(value.max));
+        map.insert("p95".into(), JsonValue::Number(value.p95));
+        map.insert("stddev".into(), JsonValue::Number(value.stddev));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Stats {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected stats to be a JSON object.")?;
+
+        let get = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected stats.{key} to be a number."))
+        };
+
+        Ok(Stats {
+            mean: get("mean")?,
+            median: get("median")?,
+            min: get("min")?,
+            max: get("max")?,
+            p95: get("p95")?,
+            stddev: get("stddev")?,
+        })
+    }
+}
+
+/// Serialize optional stats, mapping `None` to `null`.
+pub fn to_json(stats: Option<&Stats>) -> JsonValue {
+    stats.map_or(JsonValue::Null, JsonValue::from)
+}
+
+/// Deserialize optional stats. Missing keys and `null` both map to `None`.
+pub fn from_json(value: Option<&JsonValue>) -> Result<Option<Stats>, String> {
+    match value {
+        None => Ok(None),
+        Some(v) if v.is_null() => Ok(None),
+        Some(v) => Stats::try_from(v).map(Some),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Stats;
+    use std::time::Duration;
+
+    fn nanos(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|x| Duration::from_nanos(*x)).collect()
+    }
+
+    #[test]
+    fn computes_stats() {
+        let stats = Stats::from_samples(&nanos(&[5, 1, 4, 2, 3])).unwrap();
+        assert_eq!(stats.mean, 3.0);
+        assert_eq!(stats.median, 3.0);
+        assert_eq!(stats.min, 1.0);
+        assert_eq!(stats.max, 5.0);
+        assert_eq!(stats.p95, 4.8);
+        assert_eq!(stats.stddev, 2_f64.sqrt());
+    }
+
+    #[test]
+    fn median_is_robust_to_outliers() {
+        let stats = Stats::from_samples(&nanos(&[10, 10, 11, 10, 10_000])).unwrap();
+        assert_eq!(stats.median, 10.0);
+        assert!(stats.mean > 2000.0);
+    }
+
+    #[test]
+    fn interpolates_even_sample_counts() {
+        let stats = Stats::from_samples(&nanos(&[1, 2, 3, 4])).unwrap();
+        assert_eq!(stats.median, 2.5);
+    }
+
+    #[test]
+    fn handles_empty_samples() {
+        assert_eq!(Stats::from_samples(&[]), None);
+    }
+}
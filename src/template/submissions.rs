@@ -0,0 +1,415 @@
+/// Log of submitted answers, used to refuse guesses that are already known to be wrong.
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::{Day, Workspace};
+
+/// Stored in the data folder of each workspace, e.g. `data/2024/submissions.jsonl`.
+static SUBMISSIONS_FILE_NAME: &str = "submissions.jsonl";
+
+/// How the website responded to a submitted answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// The answer is wrong, without a hint in which direction.
+    Wrong,
+    /// The part was already solved, so the answer was not checked.
+    AlreadySolved,
+    /// The answer was not checked because the previous one was submitted too recently.
+    Wait {
+        seconds: Option<u64>,
+    },
+}
+
+impl Outcome {
+    /// Classify the response to a submission. Returns `None` if the response is not recognized.
+    pub fn from_response(message: &str) -> Option<Self> {
+        if message.contains("That's the right answer") {
+            Some(Outcome::Correct)
+        } else if message.contains("You gave an answer too recently") {
+            Some(Outcome::Wait {
+                seconds: parse_wait(message),
+            })
+        } else if message.contains("You don't seem to be solving the right level") {
+            Some(Outcome::AlreadySolved)
+        } else if message.contains("That's not the right answer") {
+            if message.contains("answer is too high") {
+                Some(Outcome::TooHigh)
+            } else if message.contains("answer is too low") {
+                Some(Outcome::TooLow)
+            } else {
+                Some(Outcome::Wrong)
+            }
+        } else {
+            None
+        }
+    }
+
+    /// Whether the website rejected the answer itself.
+    pub fn is_wrong(self) -> bool {
+        matches!(self, Outcome::TooHigh | Outcome::TooLow | Outcome::Wrong)
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Outcome::Correct => "correct",
+            Outcome::TooHigh => "too_high",
+            Outcome::TooLow => "too_low",
+            Outcome::Wrong => "wrong",
+            Outcome::AlreadySolved => "already_solved",
+            Outcome::Wait { .. } => "wait",
+        }
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "✔ correct"),
+            Outcome::TooHigh => write!(f, "✘ too high"),
+            Outcome::TooLow => write!(f, "✘ too low"),
+            Outcome::Wrong => write!(f, "✘ wrong"),
+            Outcome::AlreadySolved => write!(f, "already solved"),
+            Outcome::Wait { seconds: Some(s) } => write!(f, "⏱ wait {s}s"),
+            Outcome::Wait { seconds: None } => write!(f, "⏱ wait"),
+        }
+    }
+}
+
+/// Parse the remaining wait time from a response, e.g. `You have 1m 5s left to wait.`
+fn parse_wait(message: &str) -> Option<u64> {
+    let start = message.find("You have ")? + "You have ".len();
+    let len = message[start..].find(" left to wait")?;
+
+    message[start..start + len]
+        .split_whitespace()
+        .map(|part| {
+            let (value, unit) = part.split_at(part.find(|c: char| !c.is_ascii_digit())?);
+            let value: u64 = value.parse().ok()?;
+            match unit {
+                "s" => Some(value),
+                "m" => Some(value * 60),
+                "h" => Some(value * 3600),
+                _ => None,
+            }
+        })
+        .sum()
+}
+
+/// A single submitted answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Submission {
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    pub day: Day,
+    pub part: u8,
+    pub answer: String,
+    pub outcome: Outcome,
+}
+
+impl Submission {
+    pub fn new(day: Day, part: u8, answer: &str, outcome: Outcome) -> Self {
+        Self {
+            timestamp: now(),
+            day,
+            part,
+            answer: answer.into(),
+            outcome,
+        }
+    }
+}
+
+/// All submissions made so far, oldest first.
+#[derive(Clone, Debug, Default)]
+pub struct Submissions {
+    pub data: Vec<Submission>,
+}
+
+impl Submissions {
+    /// Read the log of a workspace. If not present, returns an empty log.
+    pub fn read_from_file(workspace: &Workspace) -> Result<Self, String> {
+        let Ok(s) = fs::read_to_string(workspace.data_dir().join(SUBMISSIONS_FILE_NAME)) else {
+            return Ok(Self::default());
+        };
+        parse(&s)
+    }
+
+    /// Check whether an answer is worth submitting, based on previous submissions of the same part.
+    /// Returns the reason if it is not.
+    pub fn check(&self, day: Day, part: u8, answer: &str) -> Result<(), String> {
+        let previous = || {
+            self.data
+                .iter()
+                .filter(move |s| s.day == day && s.part == part)
+        };
+
+        if let Some(s) = previous().find(|s| s.outcome == Outcome::Correct) {
+            return Err(format!("this part was already solved with `{}`.", s.answer));
+        }
+
+        if previous().any(|s| s.answer == answer && s.outcome.is_wrong()) {
+            return Err(format!("`{answer}` was already submitted and is wrong."));
+        }
+
+        let now = now();
+        if let Some(wait) = previous()
+            .filter_map(|s| match s.outcome {
+                Outcome::Wait {
+                    seconds: Some(seconds),
+                } => Some(s.timestamp + seconds),
+                _ => None,
+            })
+            .max()
+            .filter(|until| *until > now)
+        {
+            return Err(format!("wait {}s before submitting again.", wait - now));
+        }
+
+        // numeric answers can be compared against known bounds.
+        let Ok(value) = answer.parse::<i128>() else {
+            return Ok(());
+        };
+
+        for s in previous() {
+            let Ok(bound) = s.answer.parse::<i128>() else {
+                continue;
+            };
+
+            match s.outcome {
+                Outcome::TooHigh if value >= bound => {
+                    return Err(format!("`{}` was too high.", s.answer));
+                }
+                Outcome::TooLow if value <= bound => {
+                    return Err(format!("`{}` was too low.", s.answer));
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Append a submission to the log file of a workspace.
+pub fn append(workspace: &Workspace, submission: &Submission) -> Result<(), io::Error> {
+    let line = JsonValue::from(submission)
+        .stringify()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+
+    // the data folder of a year namespaced by year may not exist yet.
+    fs::create_dir_all(workspace.data_dir())?;
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(workspace.data_dir().join(SUBMISSIONS_FILE_NAME))?;
+
+    writeln!(file, "{line}")
+}
+
+fn parse(s: &str) -> Result<Submissions, String> {
+    Ok(Submissions {
+        data: s
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .map(|l| {
+                let json = JsonValue::from_str(l).or(Err("submission is not valid JSON."))?;
+                Submission::try_from(&json)
+            })
+            .collect::<Result<_, _>>()?,
+    })
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Submission> for JsonValue {
+    fn from(value: &Submission) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert(
+            "outcome".into(),
+            JsonValue::String(value.outcome.as_str().into()),
+        );
+
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "wait_seconds".into(),
+            match value.outcome {
+                Outcome::Wait { seconds: Some(s) } => JsonValue::Number(s as f64),
+                _ => JsonValue::Null,
+            },
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Submission {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected submission to be a JSON object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>())
+                .map(|v| *v as u64)
+        };
+
+        let timestamp =
+            number("timestamp").ok_or("Expected submission.timestamp to be a number.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected submission.day to be a Day struct.")?;
+
+        let part = number("part")
+            .and_then(|v| u8::try_from(v).ok())
+            .ok_or("Expected submission.part to be a number.")?;
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.answer to be a string.")?;
+
+        let outcome = match json.get("outcome").and_then(|v| v.get::<String>()) {
+            Some(s) if s == "correct" => Outcome::Correct,
+            Some(s) if s == "too_high" => Outcome::TooHigh,
+            Some(s) if s == "too_low" => Outcome::TooLow,
+            Some(s) if s == "wrong" => Outcome::Wrong,
+            Some(s) if s == "already_solved" => Outcome::AlreadySolved,
+            Some(s) if s == "wait" => Outcome::Wait {
+                seconds: number("wait_seconds"),
+            },
+            _ => return Err("Expected submission.outcome to be a known outcome.".into()),
+        };
+
+        Ok(Submission {
+            timestamp,
+            day,
+            part,
+            answer: answer.clone(),
+            outcome,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse, Outcome, Submission, Submissions};
+    use crate::day;
+    use tinyjson::JsonValue;
+
+    fn submission(part: u8, answer: &str, outcome: Outcome) -> Submission {
+        Submission {
+            timestamp: 1_733_011_200,
+            day: day!(1),
+            part,
+            answer: answer.into(),
+            outcome,
+        }
+    }
+
+    #[test]
+    fn classifies_responses() {
+        let cases = [
+            (
+                "That's the right answer! You are one gold star closer.",
+                Outcome::Correct,
+            ),
+            (
+                "That's not the right answer; your answer is too high.",
+                Outcome::TooHigh,
+            ),
+            (
+                "That's not the right answer; your answer is too low.",
+                Outcome::TooLow,
+            ),
+            (
+                "That's not the right answer. If you're stuck, ...",
+                Outcome::Wrong,
+            ),
+            (
+                "You don't seem to be solving the right level.  Did you already complete it?",
+                Outcome::AlreadySolved,
+            ),
+            (
+                "You gave an answer too recently. You have 1m 5s left to wait.",
+                Outcome::Wait { seconds: Some(65) },
+            ),
+        ];
+
+        for (message, outcome) in cases {
+            assert_eq!(Outcome::from_response(message), Some(outcome), "{message}");
+        }
+
+        assert_eq!(Outcome::from_response("<html>"), None);
+    }
+
+    #[test]
+    fn refuses_known_wrong_answers() {
+        let log = Submissions {
+            data: vec![
+                submission(1, "100", Outcome::TooHigh),
+                submission(1, "10", Outcome::TooLow),
+                submission(1, "50", Outcome::Wrong),
+                submission(2, "abc", Outcome::Wrong),
+            ],
+        };
+
+        assert!(log.check(day!(1), 1, "50").is_err());
+        assert!(log.check(day!(1), 1, "100").is_err());
+        assert!(log.check(day!(1), 1, "120").is_err());
+        assert!(log.check(day!(1), 1, "10").is_err());
+        assert!(log.check(day!(1), 1, "42").is_ok());
+        assert!(log.check(day!(1), 2, "abc").is_err());
+        assert!(log.check(day!(1), 2, "abd").is_ok());
+        assert!(log.check(day!(2), 1, "50").is_ok());
+    }
+
+    #[test]
+    fn refuses_solved_parts() {
+        let log = Submissions {
+            data: vec![submission(1, "42", Outcome::Correct)],
+        };
+        assert!(log.check(day!(1), 1, "42").is_err());
+        assert!(log.check(day!(1), 2, "42").is_ok());
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let entry = submission(2, "42", Outcome::Wait { seconds: Some(30) });
+        let line = JsonValue::from(&entry).stringify().unwrap();
+        let log = parse(&format!("{line}\n")).unwrap();
+        assert_eq!(log.data, vec![entry]);
+    }
+}
@@ -0,0 +1,187 @@
+/// Settings that control the layout of the benchmark table, read from `data/table.json`.
+///
+/// ```json
+/// { "columns": ["part_1", "part_2", "total", "share", "sparkline"], "sort": "cost" }
+/// ```
+use std::{collections::HashMap, fs, str::FromStr};
+
+use tinyjson::JsonValue;
+
+static SETTINGS_FILE_PATH: &str = "./data/table.json";
+
+/// A column of the benchmark table, following the `Day` column.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Column {
+    /// The runtime of the shared `parse` stage. Only shown if a day parses its input once.
+    Parse,
+    Part1,
+    Part2,
+    /// The combined runtime of both parts.
+    Total,
+    /// The share of the day in the runtime of all days.
+    Share,
+    /// The number of samples each part was benched with.
+    Samples,
+    /// The larger peak heap usage of both parts. Only shown if heap stats were recorded.
+    Heap,
+    /// The number of allocations of both parts. Only shown if heap stats were recorded.
+    Allocations,
+    /// A link to the puzzle description in `data/puzzles`.
+    Puzzle,
+    /// A bar that shows the runtime of the day relative to the slowest day.
+    Sparkline,
+}
+
+impl Column {
+    pub fn title(self) -> &'static str {
+        match self {
+            Column::Parse => "Parse",
+            Column::Part1 => "Part 1",
+            Column::Part2 => "Part 2",
+            Column::Total => "Total",
+            Column::Share => "Share",
+            Column::Samples => "Samples",
+            Column::Heap => "Peak heap",
+            Column::Allocations => "Allocations",
+            Column::Puzzle => "Puzzle",
+            Column::Sparkline => "Runtime",
+        }
+    }
+}
+
+impl FromStr for Column {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "parse" => Ok(Column::Parse),
+            "part_1" => Ok(Column::Part1),
+            "part_2" => Ok(Column::Part2),
+            "total" => Ok(Column::Total),
+            "share" => Ok(Column::Share),
+            "samples" => Ok(Column::Samples),
+            "heap" => Ok(Column::Heap),
+            "allocations" => Ok(Column::Allocations),
+            "puzzle" => Ok(Column::Puzzle),
+            "sparkline" => Ok(Column::Sparkline),
+            s => Err(format!("unknown table column `{s}`.")),
+        }
+    }
+}
+
+/// The order of the rows of each year.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Sort {
+    #[default]
+    Day,
+    /// Slowest day first.
+    Cost,
+}
+
+impl FromStr for Sort {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "day" => Ok(Sort::Day),
+            "cost" => Ok(Sort::Cost),
+            s => Err(format!(
+                "unknown table sort order `{s}`, expected day or cost."
+            )),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TableSettings {
+    pub columns: Vec<Column>,
+    pub sort: Sort,
+}
+
+impl Default for TableSettings {
+    fn default() -> Self {
+        Self {
+            columns: vec![Column::Parse, Column::Part1, Column::Part2, Column::Heap],
+            sort: Sort::Day,
+        }
+    }
+}
+
+impl TableSettings {
+    /// Read the settings file. If not present, returns the default settings.
+    pub fn read_from_file() -> Self {
+        let Ok(s) = fs::read_to_string(SETTINGS_FILE_PATH) else {
+            return Self::default();
+        };
+
+        match Self::try_from(s) {
+            Ok(settings) => settings,
+            Err(e) => {
+                eprintln!("{SETTINGS_FILE_PATH}: {e}");
+                Self::default()
+            }
+        }
+    }
+}
+
+impl TryFrom<String> for TableSettings {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        let mut settings = Self::default();
+
+        if let Some(columns) = json.get("columns") {
+            settings.columns = columns
+                .get::<Vec<JsonValue>>()
+                .ok_or("expected `columns` to be an array.")?
+                .iter()
+                .map(|c| {
+                    c.get::<String>()
+                        .ok_or("expected `columns` to contain strings.".to_string())
+                        .and_then(|c| c.parse())
+                })
+                .collect::<Result<_, _>>()?;
+        }
+
+        if let Some(sort) = json.get("sort") {
+            settings.sort = sort
+                .get::<String>()
+                .ok_or("expected `sort` to be a string.")?
+                .parse()?;
+        }
+
+        Ok(settings)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Column, Sort, TableSettings};
+
+    #[test]
+    fn parses_settings() {
+        let json = r#"{ "columns": ["total", "sparkline"], "sort": "cost" }"#.to_string();
+        let settings = TableSettings::try_from(json).unwrap();
+        assert_eq!(settings.columns, vec![Column::Total, Column::Sparkline]);
+        assert_eq!(settings.sort, Sort::Cost);
+
+        // omitted settings keep their default.
+        let settings = TableSettings::try_from(r#"{ "sort": "day" }"#.to_string()).unwrap();
+        assert_eq!(settings.columns, TableSettings::default().columns);
+    }
+
+    #[test]
+    fn rejects_unknown_settings() {
+        assert!(TableSettings::try_from(r#"{ "columns": ["foo"] }"#.to_string()).is_err());
+        assert!(TableSettings::try_from(r#"{ "sort": "name" }"#.to_string()).is_err());
+        assert!(TableSettings::try_from(r#"{ "columns": "total" }"#.to_string()).is_err());
+    }
+}
@@ -0,0 +1,190 @@
+/// Templates that `scaffold` creates solutions from.
+///
+/// Built-in templates live in `src/templates`. Templates in `data/templates` take precedence and can
+/// add new names or replace built-in ones. `AOC_TEMPLATE` selects the template used without `--template`.
+///
+/// Templates may start with directives, e.g. `%% answer_type = u64` or `%% examples = 2`.
+use std::{env, fs, path::Path};
+
+use crate::template::{Day, Year};
+
+/// Selects the default template, e.g. in `.cargo/config.toml`.
+pub const TEMPLATE_ENV: &str = "AOC_TEMPLATE";
+
+static USER_TEMPLATES_DIR: &str = "./data/templates";
+static DEFAULT_TEMPLATE: &str = "default";
+static DEFAULT_ANSWER_TYPE: &str = "u32";
+
+macro_rules! built_in {
+    ($name:literal) => {
+        (
+            $name,
+            include_str!(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/src/templates/",
+                $name,
+                ".txt"
+            )),
+        )
+    };
+}
+
+static BUILT_IN: [(&str, &str); 4] = [
+    built_in!("default"),
+    built_in!("grid"),
+    built_in!("numbers"),
+    built_in!("sections"),
+];
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Template {
+    body: String,
+    /// The type of the answers, used for `%ANSWER_TYPE%`.
+    pub answer_type: String,
+    /// The number of example files to create, e.g. `01.txt` and `01-2.txt` for 2.
+    pub examples: u8,
+}
+
+/// Values that are filled into the placeholders of a template.
+pub struct Placeholders<'a> {
+    pub day: Day,
+    pub year: Option<Year>,
+    /// The title of the puzzle, e.g. `Day 1: Historian Hysteria`.
+    pub title: &'a str,
+}
+
+impl Template {
+    /// Load the template `name`, or the default template if `None`.
+    pub fn load(name: Option<&str>) -> Result<Self, String> {
+        let env_name = env::var(TEMPLATE_ENV).ok().filter(|s| !s.is_empty());
+        let name = name.or(env_name.as_deref()).unwrap_or(DEFAULT_TEMPLATE);
+
+        let user_path = Path::new(USER_TEMPLATES_DIR).join(format!("{name}.txt"));
+        if let Ok(source) = fs::read_to_string(&user_path) {
+            return Self::parse(&source).map_err(|e| format!("{}: {e}", user_path.display()));
+        }
+
+        match BUILT_IN.iter().find(|(n, _)| *n == name) {
+            Some((_, source)) => Self::parse(source),
+            None => Err(format!(
+                "unknown template `{name}`, available templates: {}.",
+                available().join(", ")
+            )),
+        }
+    }
+
+    /// Parse a template, reading the directives at its start.
+    pub fn parse(source: &str) -> Result<Self, String> {
+        let mut template = Self {
+            body: String::new(),
+            answer_type: DEFAULT_ANSWER_TYPE.into(),
+            examples: 1,
+        };
+
+        let mut lines = source.lines().peekable();
+
+        while let Some(directive) = lines.peek().and_then(|line| line.strip_prefix("%%")) {
+            let (key, value) = directive
+                .split_once('=')
+                .map(|(key, value)| (key.trim(), value.trim()))
+                .ok_or(format!("expected `%% key = value`, found `%%{directive}`."))?;
+
+            match key {
+                "answer_type" => template.answer_type = value.into(),
+                "examples" => {
+                    template.examples = value.parse().ok().filter(|n| *n > 0).ok_or(format!(
+                        "expected `examples` to be a positive number, found `{value}`."
+                    ))?;
+                }
+                key => return Err(format!("unknown template directive `{key}`.")),
+            }
+
+            lines.next();
+        }
+
+        template.body = lines.map(|line| format!("{line}\n")).collect();
+        Ok(template)
+    }
+
+    /// Fill in the placeholders of the template.
+    pub fn render(&self, placeholders: &Placeholders) -> String {
+        let year = placeholders
+            .year
+            .map(|year| year.to_string())
+            .unwrap_or_default();
+
+        self.body
+            .replace("%DAY_NUMBER%", &placeholders.day.into_inner().to_string())
+            .replace("%DAY%", &placeholders.day.to_string())
+            .replace("%YEAR%", &year)
+            .replace("%TITLE%", placeholders.title)
+            .replace("%ANSWER_TYPE%", &self.answer_type)
+    }
+}
+
+/// Names of the built-in templates and of the templates in `data/templates`.
+pub fn available() -> Vec<String> {
+    let mut names: Vec<String> = BUILT_IN.iter().map(|(name, _)| name.to_string()).collect();
+
+    if let Ok(entries) = fs::read_dir(USER_TEMPLATES_DIR) {
+        for entry in entries.filter_map(Result::ok) {
+            let path = entry.path();
+            if path.extension().is_some_and(|ext| ext == "txt") {
+                if let Some(name) = path.file_stem().and_then(|s| s.to_str()) {
+                    names.push(name.into());
+                }
+            }
+        }
+    }
+
+    names.sort();
+    names.dedup();
+    names
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Placeholders, Template, BUILT_IN};
+    use crate::{day, template::Year};
+
+    #[test]
+    fn renders_placeholders() {
+        let template = Template::parse(
+            "%% answer_type = u64\n%% examples = 2\nsolution!(%DAY_NUMBER%); // %DAY% %YEAR% %TITLE%\nfn f() -> Option<%ANSWER_TYPE%>\n",
+        )
+        .unwrap();
+
+        assert_eq!(template.examples, 2);
+
+        let rendered = template.render(&Placeholders {
+            day: day!(5),
+            year: Year::new(2024),
+            title: "Day 5: Print Queue",
+        });
+
+        assert_eq!(
+            rendered,
+            "solution!(5); // 05 2024 Day 5: Print Queue\nfn f() -> Option<u64>\n"
+        );
+    }
+
+    #[test]
+    fn parses_built_in_templates() {
+        for (name, source) in BUILT_IN {
+            let template = Template::parse(source).unwrap();
+            assert!(
+                template.body.contains("solution!(%DAY_NUMBER%)"),
+                "template `{name}` does not set up the solution."
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_directives() {
+        assert!(Template::parse("%% answer_type u64\n").is_err());
+        assert!(Template::parse("%% examples = 0\n").is_err());
+        assert!(Template::parse("%% inputs = 2\n").is_err());
+    }
+}
@@ -0,0 +1,111 @@
+/// Wall-clock limits for running a part of a solution.
+use std::{collections::HashMap, env, time::Duration};
+
+use crate::template::Day;
+
+pub const TIMEOUT_ENV: &str = "AOC_TIMEOUT_MS";
+
+/// Exit status of a solution that was cancelled because a part exceeded its timeout.
+/// Mirrors the exit status of the `timeout` command-line utility.
+pub const TIMEOUT_EXIT_CODE: i32 = 124;
+
+/// Label that is shown instead of a duration for parts that timed out.
+pub const TIMEOUT_LABEL: &str = "⏱ timeout";
+
+/// Per-part timeouts, with optional overrides for individual days.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Timeouts {
+    pub default: Option<Duration>,
+    pub days: HashMap<Day, Duration>,
+}
+
+impl Timeouts {
+    /// Parse timeouts from a list of `<ms>` or `<day>=<ms>` values, e.g. `["5000", "07=60000"]`.
+    pub fn parse(values: &[String]) -> Result<Self, String> {
+        let mut timeouts = Self::default();
+
+        for value in values {
+            let invalid = || format!("invalid timeout `{value}`, expected `<ms>` or `<day>=<ms>`.");
+
+            match value.split_once('=') {
+                Some((day, millis)) => {
+                    let day: Day = day.parse().map_err(|_| invalid())?;
+                    let millis: u64 = millis.parse().map_err(|_| invalid())?;
+                    timeouts.days.insert(day, Duration::from_millis(millis));
+                }
+                None => {
+                    let millis: u64 = value.parse().map_err(|_| invalid())?;
+                    timeouts.default = Some(Duration::from_millis(millis));
+                }
+            }
+        }
+
+        Ok(timeouts)
+    }
+
+    /// Read the timeouts from `AOC_TIMEOUT_MS` and the per-day `AOC_TIMEOUT_MS_<day>` variables.
+    pub fn from_env() -> Self {
+        let millis = |key: &str| env::var(key).ok()?.parse().ok().map(Duration::from_millis);
+
+        Self {
+            default: millis(TIMEOUT_ENV),
+            days: crate::template::all_days()
+                .filter_map(|day| Some((day, millis(&format!("{TIMEOUT_ENV}_{day}"))?)))
+                .collect(),
+        }
+    }
+
+    /// The timeout that applies to each part of `day`, if any.
+    pub fn for_day(&self, day: Day) -> Option<Duration> {
+        self.days.get(&day).copied().or(self.default)
+    }
+
+    /// Environment variables that pass these timeouts on to a child process.
+    pub fn env_vars(&self) -> Vec<(String, String)> {
+        let mut vars: Vec<(String, String)> = self
+            .days
+            .iter()
+            .map(|(day, timeout)| {
+                (
+                    format!("{TIMEOUT_ENV}_{day}"),
+                    timeout.as_millis().to_string(),
+                )
+            })
+            .collect();
+
+        if let Some(timeout) = self.default {
+            vars.push((TIMEOUT_ENV.into(), timeout.as_millis().to_string()));
+        }
+
+        vars
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Timeouts;
+    use crate::day;
+    use std::time::Duration;
+
+    #[test]
+    fn parses_default_and_day_timeouts() {
+        let timeouts = Timeouts::parse(&["5000".into(), "07=60000".into()]).unwrap();
+        assert_eq!(timeouts.for_day(day!(1)), Some(Duration::from_secs(5)));
+        assert_eq!(timeouts.for_day(day!(7)), Some(Duration::from_secs(60)));
+    }
+
+    #[test]
+    fn handles_missing_timeouts() {
+        let timeouts = Timeouts::parse(&["7=100".into()]).unwrap();
+        assert_eq!(timeouts.for_day(day!(1)), None);
+        assert_eq!(timeouts.for_day(day!(7)), Some(Duration::from_millis(100)));
+    }
+
+    #[test]
+    fn rejects_invalid_timeouts() {
+        assert!(Timeouts::parse(&["5s".into()]).is_err());
+        assert!(Timeouts::parse(&["26=100".into()]).is_err());
+    }
+}
@@ -1,17 +1,111 @@
 use std::{collections::HashMap, fs, io::Error, str::FromStr};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::{
+    bench_config::{self, BenchConfig},
+    heap::{self, HeapStats},
+    results::{ERROR_LABEL, PARSE_PART},
+    stats,
+    stats::Stats,
+    timeout::TIMEOUT_LABEL,
+    Day, Year,
+};
 
 static TIMINGS_FILE_PATH: &str = "./data/timings.json";
 
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
 pub struct Timing {
+    /// Absent in timings stored by older versions.
+    pub year: Option<Year>,
     pub day: Day,
     pub part_1: Option<String>,
     pub part_2: Option<String>,
+    /// Runtime of the solved parts, including parsing the input.
     pub total_nanos: f64,
+    /// Full benchmark statistics per part, absent in timings stored by older versions.
+    pub part_1_stats: Option<Stats>,
+    pub part_2_stats: Option<Stats>,
+    /// Allocations per part, only present if recorded with the `heap-stats` feature.
+    pub part_1_heap: Option<HeapStats>,
+    pub part_2_heap: Option<HeapStats>,
+    /// Number of samples each part was benched with, absent in timings stored by older versions.
+    pub part_1_samples: Option<u64>,
+    pub part_2_samples: Option<u64>,
+    /// Duration of parsing the input, only present for solutions with a shared `parse` stage.
+    pub parse: Option<String>,
+    pub parse_stats: Option<Stats>,
+    /// The benchmark settings the timings were recorded with.
+    pub bench_config: Option<BenchConfig>,
+}
+
+impl Timing {
+    /// A timing of a day without any recorded parts.
+    pub fn new(year: Option<Year>, day: Day) -> Self {
+        Timing {
+            year,
+            day,
+            part_1: None,
+            part_2: None,
+            total_nanos: 0_f64,
+            part_1_stats: None,
+            part_2_stats: None,
+            part_1_heap: None,
+            part_2_heap: None,
+            part_1_samples: None,
+            part_2_samples: None,
+            parse: None,
+            parse_stats: None,
+            bench_config: None,
+        }
+    }
+
+    /// Duration of a part in nanoseconds, or of parsing the input for [`PARSE_PART`].
+    /// Prefers the full statistics and falls back to parsing the formatted duration of older timings.
+    pub fn part_nanos(&self, part: u8, median: bool) -> Option<f64> {
+        let (formatted, stats) = match part {
+            PARSE_PART => (self.parse.as_deref(), self.parse_stats),
+            1 => (self.part_1.as_deref(), self.part_1_stats),
+            2 => (self.part_2.as_deref(), self.part_2_stats),
+            _ => return None,
+        };
+
+        match stats {
+            Some(stats) if median => Some(stats.median),
+            Some(stats) => Some(stats.mean),
+            None => formatted.and_then(parse_duration),
+        }
+    }
+}
+
+/// The change in duration of one part between two sets of timings.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartDelta {
+    pub day: Day,
+    pub part: u8,
+    pub old_nanos: f64,
+    pub new_nanos: f64,
+}
+
+impl PartDelta {
+    /// Relative change in percent. Positive values mean that the part got slower.
+    pub fn percent(&self) -> f64 {
+        if self.old_nanos == 0.0 {
+            return 0.0;
+        }
+        (self.new_nanos - self.old_nanos) / self.old_nanos * 100.0
+    }
+}
+
+/// Parse a duration as formatted by `Debug` back to nanoseconds, e.g. `74.13µs`.
+fn parse_duration(s: &str) -> Option<f64> {
+    // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+    let parse = |postfix: &str| s.strip_suffix(postfix)?.parse::<f64>().ok();
+
+    parse("ns")
+        .or_else(|| parse("µs").map(|x| x * 1_000_f64))
+        .or_else(|| parse("ms").map(|x| x * 1_000_000_f64))
+        .or_else(|| parse("s").map(|x| x * 1_000_000_000_f64))
 }
 
 /// Represents benchmark times for a set of days.
@@ -36,7 +130,10 @@ impl Timings {
             .and_then(Timings::try_from);
 
         match s {
-            Ok(timings) => timings,
+            Ok(mut timings) => {
+                timings.assign_year(Year::from_env());
+                timings
+            }
             Err(e) => {
                 eprintln!("{e}");
                 Timings::default()
@@ -44,6 +141,27 @@ impl Timings {
         }
     }
 
+    /// Assign `year` to timings that were stored before timings were keyed by year.
+    /// These belong to the year configured in `AOC_YEAR`.
+    pub fn assign_year(&mut self, year: Option<Year>) {
+        self.data
+            .iter_mut()
+            .filter(|t| t.year.is_none())
+            .for_each(|t| t.year = year);
+    }
+
+    /// The timings of a single year.
+    pub fn for_year(&self, year: Option<Year>) -> Self {
+        Timings {
+            data: self
+                .data
+                .iter()
+                .filter(|t| t.year == year)
+                .cloned()
+                .collect(),
+        }
+    }
+
     /// Merge two sets of timings, overwriting `self` with `other` if present.
     pub fn merge(&self, new: &Self) -> Self {
         let mut data: Vec<Timing> = vec![];
@@ -53,12 +171,15 @@ impl Timings {
         }
 
         for timing in &self.data {
-            if !data.iter().any(|t| t.day == timing.day) {
+            if !data
+                .iter()
+                .any(|t| t.year == timing.year && t.day == timing.day)
+            {
                 data.push(timing.clone());
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|t| (t.year, t.day));
         Timings { data }
     }
 
@@ -67,10 +188,48 @@ impl Timings {
         self.data.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
     }
 
-    pub fn is_day_complete(&self, day: Day) -> bool {
-        self.data
-            .iter()
-            .any(|t| t.day == day && t.part_1.is_some() && t.part_2.is_some())
+    /// Compare `new` timings against `self`, yielding one entry per part present in both.
+    /// Parsing the input counts as a part, see [`PARSE_PART`].
+    /// Uses the median if `median` is set and both sides have full statistics, the mean otherwise.
+    pub fn compare(&self, new: &Self, median: bool) -> Vec<PartDelta> {
+        let mut deltas = vec![];
+
+        for new_timing in &new.data {
+            let Some(old_timing) = self
+                .data
+                .iter()
+                .find(|t| t.year == new_timing.year && t.day == new_timing.day)
+            else {
+                continue;
+            };
+
+            for part in [PARSE_PART, 1, 2] {
+                let old = old_timing.part_nanos(part, median);
+                let new = new_timing.part_nanos(part, median);
+
+                if let (Some(old_nanos), Some(new_nanos)) = (old, new) {
+                    deltas.push(PartDelta {
+                        day: new_timing.day,
+                        part,
+                        old_nanos,
+                        new_nanos,
+                    });
+                }
+            }
+        }
+
+        deltas
+    }
+
+    pub fn is_day_complete(&self, year: Option<Year>, day: Day) -> bool {
+        let is_complete = |part: &Option<String>| {
+            part.as_deref()
+                .is_some_and(|p| p != TIMEOUT_LABEL && p != ERROR_LABEL)
+        };
+
+        self.data.iter().any(|t| {
+            t.year == year && t.day == day && is_complete(&t.part_1) && is_complete(&t.part_2)
+        })
     }
 }
 
@@ -118,6 +277,9 @@ impl From<&Timing> for JsonValue {
     fn from(value: &Timing) -> Self {
         let mut map: HashMap<String, JsonValue> = HashMap::new();
 
+        if let Some(year) = value.year {
+            map.insert("year".into(), JsonValue::String(year.to_string()));
+        }
         map.insert("day".into(), JsonValue::String(value.day.to_string()));
         map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
 
@@ -140,6 +302,46 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        map.insert(
+            "part_1_stats".into(),
+            stats::to_json(value.part_1_stats.as_ref()),
+        );
+        map.insert(
+            "part_2_stats".into(),
+            stats::to_json(value.part_2_stats.as_ref()),
+        );
+        map.insert(
+            "part_1_heap".into(),
+            heap::to_json(value.part_1_heap.as_ref()),
+        );
+        map.insert(
+            "part_2_heap".into(),
+            heap::to_json(value.part_2_heap.as_ref()),
+        );
+        #[allow(clippy::cast_precision_loss)]
+        let samples =
+            |samples: Option<u64>| samples.map_or(JsonValue::Null, |s| JsonValue::Number(s as f64));
+        map.insert("part_1_samples".into(), samples(value.part_1_samples));
+        map.insert("part_2_samples".into(), samples(value.part_2_samples));
+        map.insert(
+            "parse".into(),
+            value
+                .parse
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        map.insert(
+            "parse_stats".into(),
+            stats::to_json(value.parse_stats.as_ref()),
+        );
+        map.insert(
+            "bench_config".into(),
+            value
+                .bench_config
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+
         JsonValue::Object(map)
     }
 }
@@ -158,6 +360,15 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|day| Day::from_str(day).ok())
             .ok_or("Expected timing.day to be a Day struct.")?;
 
+        let year = match json.get("year") {
+            None => None,
+            Some(v) => Some(
+                v.get::<String>()
+                    .and_then(|year| Year::from_str(year).ok())
+                    .ok_or("Expected timing.year to be a Year struct.")?,
+            ),
+        };
+
         let part_1 = json
             .get("part_1")
             .map(|v| if v.is_null() { None } else { v.get::<String>() })
@@ -168,16 +379,56 @@ impl TryFrom<&JsonValue> for Timing {
             .map(|v| if v.is_null() { None } else { v.get::<String>() })
             .ok_or("Expected timing.part_2 to be null or string.")?;
 
+        // absent in timings stored by older versions.
+        let parse = match json.get("parse") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(
+                v.get::<String>()
+                    .ok_or("Expected timing.parse to be null or string.")?
+                    .clone(),
+            ),
+        };
+
         let total_nanos = json
             .get("total_nanos")
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        let part_1_stats = stats::from_json(json.get("part_1_stats"))?;
+        let parse_stats = stats::from_json(json.get("parse_stats"))?;
+        let part_2_stats = stats::from_json(json.get("part_2_stats"))?;
+        let part_1_heap = heap::from_json(json.get("part_1_heap"))?;
+        let part_2_heap = heap::from_json(json.get("part_2_heap"))?;
+        let bench_config = bench_config::from_json(json.get("bench_config"))?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let samples = |key: &str| match json.get(key) {
+            None => Ok(None),
+            Some(v) if v.is_null() => Ok(None),
+            Some(v) => v
+                .get::<f64>()
+                .map(|v| Some(*v as u64))
+                .ok_or(format!("Expected timing.{key} to be null or a number.")),
+        };
+        let part_1_samples = samples("part_1_samples")?;
+        let part_2_samples = samples("part_2_samples")?;
+
         Ok(Timing {
+            year,
             day,
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
             total_nanos,
+            part_1_stats,
+            part_2_stats,
+            part_1_heap,
+            part_2_heap,
+            part_1_samples,
+            part_2_samples,
+            parse,
+            parse_stats,
+            bench_config,
         })
     }
 }
@@ -194,29 +445,28 @@ mod tests {
         Timings {
             data: vec![
                 Timing {
-                    day: day!(1),
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
+                    ..Timing::new(None, day!(1))
                 },
                 Timing {
-                    day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
+                    ..Timing::new(None, day!(2))
                 },
                 Timing {
-                    day: day!(4),
                     part_1: Some("40ms".into()),
-                    part_2: None,
                     total_nanos: 4e+10,
+                    ..Timing::new(None, day!(4))
                 },
             ],
         }
     }
 
     mod deserialization {
-        use crate::{day, template::timings::Timings};
+        use crate::{day, template::timings::Timings, year};
 
         #[test]
         fn handles_json_timings() {
@@ -230,6 +480,16 @@ mod tests {
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
         }
 
+        #[test]
+        fn handles_json_timings_with_years() {
+            let json = r#"{ "data": [{ "year": "2023", "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000000 }] }"#.to_string();
+            let mut timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.data[0].year, Some(year!(2023)));
+
+            timings.assign_year(Some(year!(2024)));
+            assert_eq!(timings.data[0].year, Some(year!(2023)));
+        }
+
         #[test]
         fn handles_empty_timings() {
             let json = r#"{ "data": [] }"#.to_string();
@@ -278,49 +538,138 @@ mod tests {
     mod is_day_complete {
         use crate::{
             day,
-            template::timings::{Timing, Timings},
+            template::{
+                timeout::TIMEOUT_LABEL,
+                timings::{Timing, Timings},
+            },
         };
 
         #[test]
         fn handles_completed_days() {
             let timings = Timings {
                 data: vec![Timing {
-                    day: day!(1),
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
                     total_nanos: 3_000_000_000_f64,
+                    ..Timing::new(None, day!(1))
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(None, day!(1)), true);
         }
 
         #[test]
         fn handles_partial_days() {
             let timings = Timings {
                 data: vec![Timing {
-                    day: day!(1),
                     part_1: Some("1ms".into()),
-                    part_2: None,
                     total_nanos: 1_000_000_000_f64,
+                    ..Timing::new(None, day!(1))
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(None, day!(1)), false);
+        }
+
+        #[test]
+        fn handles_timed_out_days() {
+            let timings = Timings {
+                data: vec![Timing {
+                    part_1: Some("1ms".into()),
+                    part_2: Some(TIMEOUT_LABEL.into()),
+                    total_nanos: 1_000_000_f64,
+                    ..Timing::new(None, day!(1))
+                }],
+            };
+
+            assert_eq!(timings.is_day_complete(None, day!(1)), false);
         }
 
         #[test]
         fn handles_uncompleted_days() {
             let timings = Timings {
+                data: vec![Timing::new(None, day!(1))],
+            };
+
+            assert_eq!(timings.is_day_complete(None, day!(1)), false);
+        }
+    }
+
+    mod compare {
+        use crate::{
+            day,
+            template::{
+                results::PARSE_PART,
+                stats::Stats,
+                timings::{Timing, Timings},
+            },
+        };
+
+        use super::get_mock_timings;
+
+        #[test]
+        fn compares_overlapping_parts() {
+            let timings = get_mock_timings();
+            let other = Timings {
                 data: vec![Timing {
-                    day: day!(1),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0.0,
+                    part_1: Some("15ms".into()),
+                    total_nanos: 15_000_000_f64,
+                    ..Timing::new(None, day!(1))
                 }],
             };
+            let deltas = timings.compare(&other, false);
+            assert_eq!(deltas.len(), 1);
+            assert_eq!(deltas[0].day, day!(1));
+            assert_eq!(deltas[0].old_nanos, 10_000_000_f64);
+            assert_eq!(deltas[0].new_nanos, 15_000_000_f64);
+            assert_eq!(deltas[0].percent(), 50.0);
+        }
+
+        #[test]
+        fn compares_parse_times() {
+            let mut timings = get_mock_timings();
+            timings.data[0].parse = Some("2ms".into());
+
+            let mut other = timings.clone();
+            other.data[0].parse = Some("3ms".into());
+
+            let deltas = timings.compare(&other, false);
+            assert_eq!(deltas.len(), 6);
+            assert_eq!(deltas[0].part, PARSE_PART);
+            assert_eq!(deltas[0].old_nanos, 2_000_000_f64);
+            assert_eq!(deltas[0].new_nanos, 3_000_000_f64);
+            assert!(deltas[1..].iter().all(|delta| delta.part != PARSE_PART));
+        }
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+        #[test]
+        fn prefers_stats() {
+            let stats = Stats {
+                mean: 2_000.0,
+                median: 1_000.0,
+                min: 500.0,
+                max: 9_000.0,
+                p95: 5_000.0,
+                stddev: 100.0,
+            };
+            let timing = Timing {
+                part_1: Some("2.0µs".into()),
+                part_2: Some("1.5µs".into()),
+                total_nanos: 3_500_f64,
+                part_1_stats: Some(stats),
+                ..Timing::new(None, day!(1))
+            };
+            assert_eq!(timing.part_nanos(1, false), Some(2_000.0));
+            assert_eq!(timing.part_nanos(1, true), Some(1_000.0));
+            assert_eq!(timing.part_nanos(2, true), Some(1_500.0));
+        }
+
+        #[test]
+        fn parses_formatted_durations() {
+            let mut timing = get_mock_timings().data[0].clone();
+            for (formatted, nanos) in [("74.13ns", 74.13), ("2s", 2e9), ("1.5µs", 1_500.0)] {
+                timing.part_1 = Some(formatted.into());
+                assert_eq!(timing.part_nanos(1, false), Some(nanos));
+            }
         }
     }
 
@@ -328,6 +677,7 @@ mod tests {
         use crate::{
             day,
             template::timings::{Timing, Timings},
+            year,
         };
 
         use super::get_mock_timings;
@@ -336,12 +686,7 @@ mod tests {
         fn handles_disjunct_timings() {
             let timings = get_mock_timings();
             let other = Timings {
-                data: vec![Timing {
-                    day: day!(3),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0_f64,
-                }],
+                data: vec![Timing::new(None, day!(3))],
             };
             let merged = timings.merge(&other);
             assert_eq!(merged.data.len(), 4);
@@ -356,12 +701,7 @@ mod tests {
             let timings = get_mock_timings();
 
             let other = Timings {
-                data: vec![Timing {
-                    day: day!(2),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0_f64,
-                }],
+                data: vec![Timing::new(None, day!(2))],
             };
             let merged = timings.merge(&other);
 
@@ -387,5 +727,18 @@ mod tests {
             let merged = timings.merge(&other);
             assert_eq!(merged.data.len(), 3);
         }
+
+        #[test]
+        fn keeps_years_apart() {
+            let timings = get_mock_timings();
+            let mut other = get_mock_timings();
+            other.assign_year(Some(year!(2023)));
+
+            let merged = timings.merge(&other);
+            assert_eq!(merged.data.len(), 6);
+            assert_eq!(merged.data[0].year, None);
+            assert_eq!(merged.data[3].year, Some(year!(2023)));
+            assert_eq!(merged.for_year(Some(year!(2023))).data.len(), 3);
+        }
     }
 }
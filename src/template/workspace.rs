@@ -0,0 +1,213 @@
+/// Locations of the solutions and data of a year.
+///
+/// A year either uses the flat layout (`src/bin/01.rs`, `data/inputs/01.txt`) or is namespaced by
+/// year (`src/bin/2024-01.rs`, `data/2024/inputs/01.txt`), which allows keeping several years in one repository.
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use crate::template::{Day, Year};
+
+static BIN_DIR: &str = "src/bin";
+static DATA_DIR: &str = "data";
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Workspace {
+    /// The year of the solutions. Not known to solutions in the flat layout.
+    pub year: Option<Year>,
+    namespaced: bool,
+}
+
+impl Workspace {
+    /// The flat layout.
+    pub const fn flat(year: Option<Year>) -> Self {
+        Self {
+            year,
+            namespaced: false,
+        }
+    }
+
+    /// The layout namespaced by `year`.
+    pub const fn namespaced(year: Year) -> Self {
+        Self {
+            year: Some(year),
+            namespaced: true,
+        }
+    }
+
+    /// Locate the files of `year`.
+    ///
+    /// Solutions in the flat layout belong to the year configured in `AOC_YEAR`. That year keeps
+    /// using the flat layout as long as `src/bin` contains such solutions, every other year is namespaced.
+    pub fn locate(year: Option<Year>) -> Self {
+        match year {
+            None => Self::flat(None),
+            Some(year) if Year::from_env() == Some(year) && has_flat_bins() => {
+                Self::flat(Some(year))
+            }
+            Some(year) => Self::namespaced(year),
+        }
+    }
+
+    pub fn is_namespaced(&self) -> bool {
+        self.namespaced
+    }
+
+    /// Name of the binary that holds the solution of a day, e.g. `01` or `2024-01`.
+    pub fn bin_name(&self, day: Day) -> String {
+        match self.year {
+            Some(year) if self.namespaced => format!("{year}-{day}"),
+            _ => day.to_string(),
+        }
+    }
+
+    /// Path to the solution of a day, e.g. `./src/bin/2024-01.rs`.
+    pub fn bin_path(&self, day: Day) -> String {
+        format!("./{BIN_DIR}/{}.rs", self.bin_name(day))
+    }
+
+    /// Folder that holds the data of the year, e.g. `data/2024`.
+    pub fn data_dir(&self) -> PathBuf {
+        match self.year {
+            Some(year) if self.namespaced => Path::new(DATA_DIR).join(year.to_string()),
+            _ => PathBuf::from(DATA_DIR),
+        }
+    }
+
+    /// Path to a file in the data folder of the year, e.g. `data/2024/inputs/01.txt`.
+    pub fn data_path(&self, folder: &str, file_name: &str) -> PathBuf {
+        self.data_dir().join(folder).join(file_name)
+    }
+
+    /// Reads a text file of a day to a string, e.g. `data/2024/examples/01.txt`.
+    #[must_use]
+    pub fn read_file(&self, folder: &str, day: Day) -> String {
+        self.read(folder, &format!("{day}.txt"))
+    }
+
+    /// Reads a text file of a day to string, appending a part suffix. E.g. like `01-2.txt`.
+    #[must_use]
+    pub fn read_file_part(&self, folder: &str, day: Day, part: u8) -> String {
+        self.read(folder, &format!("{day}-{part}.txt"))
+    }
+
+    fn read(&self, folder: &str, file_name: &str) -> String {
+        let cwd = env::current_dir().unwrap();
+        let filepath = cwd.join(self.data_path(folder, file_name));
+        let f = fs::read_to_string(filepath);
+        f.expect("could not open input file")
+    }
+
+    /// Parse the name of a solution binary, e.g. `01` or `2024-01`.
+    pub fn parse_bin_name(name: &str) -> Option<(Self, Day)> {
+        match name.split_once('-') {
+            Some((year, day)) => Some((Self::namespaced(year.parse().ok()?), day.parse().ok()?)),
+            None => Some((Self::flat(None), name.parse().ok()?)),
+        }
+    }
+
+    // Not part of the public API
+    #[doc(hidden)]
+    /// The workspace of the solution at `path`, derived from its file name like `2024-01.rs`.
+    pub const fn __from_source_path(path: &str) -> Self {
+        let bytes = path.as_bytes();
+
+        let mut start = bytes.len();
+        while start > 0 && bytes[start - 1] != b'/' && bytes[start - 1] != b'\\' {
+            start -= 1;
+        }
+
+        if bytes.len() - start != "YYYY-DD.rs".len() || bytes[start + 4] != b'-' {
+            return Self::flat(None);
+        }
+
+        let mut year = 0;
+        let mut i = start;
+        while i < start + 4 {
+            if !bytes[i].is_ascii_digit() {
+                return Self::flat(None);
+            }
+            year = year * 10 + (bytes[i] - b'0') as u16;
+            i += 1;
+        }
+
+        Self::namespaced(Year::__new_unchecked(year))
+    }
+}
+
+/// Whether `src/bin` contains solutions in the flat layout.
+fn has_flat_bins() -> bool {
+    fs::read_dir(BIN_DIR).is_ok_and(|entries| {
+        entries.filter_map(Result::ok).any(|entry| {
+            let path = entry.path();
+            path.extension().is_some_and(|ext| ext == "rs")
+                && path
+                    .file_stem()
+                    .and_then(|stem| stem.to_str())
+                    .is_some_and(|stem| stem.len() == 2 && stem.parse::<Day>().is_ok())
+        })
+    })
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Workspace;
+    use crate::{day, year};
+    use std::path::Path;
+
+    #[test]
+    fn namespaces_paths_by_year() {
+        let flat = Workspace::flat(Some(year!(2024)));
+        assert_eq!(flat.bin_name(day!(1)), "01");
+        assert_eq!(flat.bin_path(day!(1)), "./src/bin/01.rs");
+        assert_eq!(
+            flat.data_path("inputs", "01.txt"),
+            Path::new("data/inputs/01.txt")
+        );
+
+        let namespaced = Workspace::namespaced(year!(2023));
+        assert_eq!(namespaced.bin_name(day!(1)), "2023-01");
+        assert_eq!(namespaced.bin_path(day!(1)), "./src/bin/2023-01.rs");
+        assert_eq!(
+            namespaced.data_path("inputs", "01.txt"),
+            Path::new("data/2023/inputs/01.txt")
+        );
+    }
+
+    #[test]
+    fn parses_bin_names() {
+        assert_eq!(
+            Workspace::parse_bin_name("07"),
+            Some((Workspace::flat(None), day!(7)))
+        );
+        assert_eq!(
+            Workspace::parse_bin_name("2023-07"),
+            Some((Workspace::namespaced(year!(2023)), day!(7)))
+        );
+        assert_eq!(Workspace::parse_bin_name("2023-26"), None);
+        assert_eq!(Workspace::parse_bin_name("registry"), None);
+    }
+
+    #[test]
+    fn derives_workspace_from_source_path() {
+        assert_eq!(
+            Workspace::__from_source_path("src/bin/2023-07.rs"),
+            Workspace::namespaced(year!(2023))
+        );
+        assert_eq!(
+            Workspace::__from_source_path("C:\\aoc\\src\\bin\\2023-07.rs"),
+            Workspace::namespaced(year!(2023))
+        );
+        assert_eq!(
+            Workspace::__from_source_path("src/bin/07.rs"),
+            Workspace::flat(None)
+        );
+        assert_eq!(
+            Workspace::__from_source_path("src/bin/abcd-07.rs"),
+            Workspace::flat(None)
+        );
+    }
+}